@@ -183,7 +183,7 @@ pub fn clone<G: Signer>(
         );
     }
 
-    let results = sync::fetch_all(id, node)?;
+    let results = sync::fetch_all(id, false, node)?;
     let Ok(repository) = storage.repository(id) else {
         // If we don't have the project locally, even after attempting to fetch,
         // there's nothing we can do.
@@ -55,7 +55,17 @@ Usage
 Show options
 
     -p, --patch                Show the actual patch diff
+        --grep <pattern>       Only show diff hunks with lines matching the pattern
+        --word-diff            Highlight the changed words of modified lines (implies --patch)
+    -U, --context <n>          Show <n> lines of context around changes (implies --patch)
+        --max-lines <n>        Summarize files with more than <n> changed lines (implies --patch)
+        --revision <id>        Show the diff of the given revision, instead of the latest (implies --patch)
+        --range <id>..<id>     Show the changes between two revisions of the patch (implies --patch)
     -v, --verbose              Show additional information about the patch
+        --json                 Output the patch as JSON, including the diff as a list of file changes
+                               if --patch is also given
+        --exit-status          Exit with a status code reflecting the patch state:
+                               0 if merged, 2 if open, 3 if draft, 4 if archived
 
 Edit options
 
@@ -98,33 +108,20 @@ pub enum OperationName {
     Redact,
 }
 
-pub struct Filter(fn(&patch::State) -> bool);
-
-impl Filter {
-    /// Match everything.
-    fn all() -> Self {
-        Self(|_| true)
-    }
-}
-
-impl Default for Filter {
-    fn default() -> Self {
-        Self(|state| matches!(state, patch::State::Open { .. }))
-    }
-}
-
-impl std::fmt::Debug for Filter {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Filter(..)")
-    }
-}
-
 #[derive(Debug)]
 pub enum Operation {
     Show {
         patch_id: Rev,
         diff: bool,
+        grep: Option<regex::Regex>,
+        word_diff: bool,
+        context: Option<u32>,
+        max_lines: Option<usize>,
+        revision: Option<Rev>,
+        interdiff: Option<(Rev, Rev)>,
         verbose: bool,
+        json: bool,
+        exit_status: bool,
     },
     Update {
         patch_id: Rev,
@@ -144,7 +141,8 @@ pub enum Operation {
         patch_id: Rev,
     },
     List {
-        filter: Filter,
+        /// Only list patches with this status, or all patches if `None`.
+        filter: Option<patch::Status>,
     },
     Edit {
         patch_id: Rev,
@@ -177,8 +175,15 @@ impl Args for Options {
         let mut revision_id = None;
         let mut message = Message::default();
         let mut push = true;
-        let mut filter = Filter::default();
+        let mut filter = Some(patch::Status::Open);
         let mut diff = false;
+        let mut grep = None;
+        let mut word_diff = false;
+        let mut context = None;
+        let mut max_lines = None;
+        let mut interdiff = None;
+        let mut json = false;
+        let mut exit_status = false;
         let mut undo = false;
 
         while let Some(arg) = parser.next()? {
@@ -217,6 +222,62 @@ impl Args for Options {
                 Long("patch") | Short('p') if op == Some(OperationName::Show) => {
                     diff = true;
                 }
+                Long("grep") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = string(&val);
+                    let pattern = regex::Regex::new(&val)
+                        .map_err(|e| anyhow!("invalid pattern '{val}': {e}"))?;
+
+                    grep = Some(pattern);
+                    diff = true;
+                }
+                Long("word-diff") if op == Some(OperationName::Show) => {
+                    word_diff = true;
+                    diff = true;
+                }
+                Long("context") | Short('U') if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = string(&val);
+                    let lines = val
+                        .parse::<u32>()
+                        .map_err(|e| anyhow!("invalid context line count '{val}': {e}"))?;
+
+                    context = Some(lines);
+                    diff = true;
+                }
+                Long("max-lines") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = string(&val);
+                    let lines = val
+                        .parse::<usize>()
+                        .map_err(|e| anyhow!("invalid line count '{val}': {e}"))?;
+
+                    max_lines = Some(lines);
+                    diff = true;
+                }
+                Long("revision") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = string(&val);
+
+                    revision_id = Some(Rev::from(val));
+                    diff = true;
+                }
+                Long("range") if op == Some(OperationName::Show) => {
+                    let val = parser.value()?;
+                    let val = string(&val);
+                    let Some((old, new)) = val.split_once("..") else {
+                        anyhow::bail!("invalid range '{val}': expected `<revision-id>..<revision-id>`");
+                    };
+
+                    interdiff = Some((Rev::from(old.to_owned()), Rev::from(new.to_owned())));
+                    diff = true;
+                }
+                Long("json") if op == Some(OperationName::Show) => {
+                    json = true;
+                }
+                Long("exit-status") if op == Some(OperationName::Show) => {
+                    exit_status = true;
+                }
 
                 // Ready options.
                 Long("undo") if op == Some(OperationName::Ready) => {
@@ -232,19 +293,19 @@ impl Args for Options {
 
                 // List options.
                 Long("all") => {
-                    filter = Filter::all();
+                    filter = None;
                 }
                 Long("draft") => {
-                    filter = Filter(|s| s == &patch::State::Draft);
+                    filter = Some(patch::Status::Draft);
                 }
                 Long("archived") => {
-                    filter = Filter(|s| s == &patch::State::Archived);
+                    filter = Some(patch::Status::Archived);
                 }
                 Long("merged") => {
-                    filter = Filter(|s| matches!(s, patch::State::Merged { .. }));
+                    filter = Some(patch::Status::Merged);
                 }
                 Long("open") => {
-                    filter = Filter(|s| matches!(s, patch::State::Open { .. }));
+                    filter = Some(patch::Status::Open);
                 }
 
                 // Common.
@@ -297,6 +358,14 @@ impl Args for Options {
                 patch_id: patch_id.ok_or_else(|| anyhow!("a patch must be provided"))?,
                 verbose,
                 diff,
+                grep,
+                word_diff,
+                context,
+                max_lines,
+                revision: revision_id,
+                interdiff,
+                json,
+                exit_status,
             },
             OperationName::Delete => Operation::Delete {
                 patch_id: patch_id.ok_or_else(|| anyhow!("a patch must be provided"))?,
@@ -347,20 +416,61 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
     transport::local::register(profile.storage.clone());
 
     if options.fetch {
-        sync::fetch_all(repository.id(), &mut Node::new(profile.socket()))?;
+        sync::fetch_all(repository.id(), false, &mut Node::new(profile.socket()))?;
     }
 
     match options.op {
-        Operation::List { filter: Filter(f) } => {
-            list::run(f, &repository, &profile)?;
+        Operation::List { filter } => {
+            list::run(filter, &repository, &profile)?;
         }
         Operation::Show {
             patch_id,
             diff,
+            grep,
+            word_diff,
+            context,
+            max_lines,
+            revision,
+            interdiff,
             verbose,
+            json,
+            exit_status,
         } => {
+            if json && grep.is_some() {
+                anyhow::bail!("`--json` and `--grep` can't be used together");
+            }
             let patch_id = patch_id.resolve(&repository.backend)?;
-            show::run(&patch_id, diff, verbose, &profile, &repository, &workdir)?;
+            let range = match (revision, interdiff) {
+                (Some(_), Some(_)) => {
+                    anyhow::bail!("`--revision` and `--range` can't be used together");
+                }
+                (Some(revision), None) => {
+                    show::DiffRange::Revision(revision.resolve(&repository.backend)?)
+                }
+                (None, Some((old, new))) => show::DiffRange::Interdiff(
+                    old.resolve(&repository.backend)?,
+                    new.resolve(&repository.backend)?,
+                ),
+                (None, None) => show::DiffRange::Latest,
+            };
+            let state = show::run(
+                &patch_id,
+                diff,
+                grep.as_ref(),
+                word_diff,
+                context,
+                max_lines,
+                &range,
+                verbose,
+                json,
+                &profile,
+                &repository,
+                &workdir,
+            )?;
+
+            if exit_status {
+                std::process::exit(show::exit_code(&state));
+            }
         }
         Operation::Update {
             ref patch_id,
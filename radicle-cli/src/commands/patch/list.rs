@@ -12,25 +12,17 @@ use term::Element as _;
 
 use super::common;
 
-/// List patches.
+/// List patches with the given status, or all patches if `None`.
 pub fn run(
-    filter: fn(&patch::State) -> bool,
+    filter: Option<patch::Status>,
     repository: &Repository,
     profile: &Profile,
 ) -> anyhow::Result<()> {
     let patches = Patches::open(repository)?;
-
-    let mut all = Vec::new();
-    for patch in patches.all()? {
-        let Ok((id, patch, _)) = patch else {
-            // Skip patches that failed to load.
-            continue;
-        };
-        if !filter(patch.state()) {
-            continue;
-        }
-        all.push((id, patch));
-    }
+    let mut all = patches
+        .filtered(filter)?
+        .map(|(id, patch, _)| (id, patch))
+        .collect::<Vec<_>>();
 
     if all.is_empty() {
         term::print(term::format::italic("Nothing to show."));
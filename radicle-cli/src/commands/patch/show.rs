@@ -1,24 +1,218 @@
-use std::process;
+use std::{io, process};
+
+use regex::Regex;
 
 use radicle::cob::patch;
 use radicle::git;
 use radicle::storage::git::Repository;
+use radicle_surf::diff::{Addition, Deletion, Diff, DiffContent, FileDiff, Hunk, Modification};
 use radicle_term::{
     table::{Table, TableOptions},
     textarea, Element, VStack,
 };
 
+use crate::commands::rad_review::diff::DiffWriter;
 use crate::terminal as term;
 
 use super::*;
 
-fn show_patch_diff(patch: &patch::Patch, stored: &Repository) -> anyhow::Result<()> {
-    let (from, to) = patch.range(stored)?;
+/// The changes of a patch that are shown as a diff.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum DiffRange {
+    /// The changes of the latest revision.
+    #[default]
+    Latest,
+    /// The changes of the given revision.
+    Revision(patch::RevisionId),
+    /// The changes between the heads of two revisions, ie. an interdiff.
+    Interdiff(patch::RevisionId, patch::RevisionId),
+}
+
+impl DiffRange {
+    /// Get the commits to diff, failing if a revision doesn't belong to the patch.
+    fn commits(
+        &self,
+        patch_id: &PatchId,
+        patch: &patch::Patch,
+        stored: &Repository,
+    ) -> anyhow::Result<(git::Oid, git::Oid)> {
+        let revision = |id: &patch::RevisionId| {
+            patch
+                .revision(id)
+                .ok_or_else(|| anyhow!("revision `{id}` does not belong to patch `{patch_id}`"))
+        };
+
+        match self {
+            Self::Latest => Ok(patch.range(stored)?),
+            Self::Revision(id) => {
+                let revision = revision(id)?;
+                Ok((*revision.base(), revision.head()))
+            }
+            Self::Interdiff(old, new) => Ok((revision(old)?.head(), revision(new)?.head())),
+        }
+    }
+}
+
+/// Check whether any line of a hunk, including context lines, matches the pattern.
+fn hunk_matches(hunk: &Hunk<Modification>, pattern: &Regex) -> bool {
+    hunk.lines.iter().any(|modification| {
+        let line = match modification {
+            Modification::Addition(Addition { line, .. }) => line,
+            Modification::Deletion(Deletion { line, .. }) => line,
+            Modification::Context { line, .. } => line,
+        };
+        pattern.is_match(&line.from_utf8_lossy())
+    })
+}
+
+/// Write only the hunks of the diff that match the pattern, along with their file headers.
+fn write_matching_hunks<W: io::Write>(
+    diff: &Diff,
+    pattern: &Regex,
+    writer: &mut DiffWriter<W>,
+) -> io::Result<usize> {
+    let mut count = 0;
+
+    for file in diff.files() {
+        let content = match file {
+            FileDiff::Modified(f) => &f.diff,
+            FileDiff::Added(f) => &f.diff,
+            FileDiff::Deleted(f) => &f.diff,
+            // Moves and copies don't have any hunks.
+            FileDiff::Moved(_) | FileDiff::Copied(_) => continue,
+        };
+        let DiffContent::Plain { hunks, .. } = content else {
+            continue;
+        };
+        let mut matching = hunks.iter().filter(|h| hunk_matches(h, pattern)).peekable();
+
+        if matching.peek().is_none() {
+            continue;
+        }
+        writer.file_header(file)?;
+
+        for hunk in matching {
+            writer.hunk(hunk)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Files with more changed lines than this are summarized, unless `--max-lines` is given.
+pub const DEFAULT_MAX_LINES: usize = 5000;
+
+/// Write all files of the diff, along with their hunks. Files with more than `max_lines`
+/// lines in their hunks are summarized instead.
+fn write_hunks<W: io::Write>(
+    diff: &Diff,
+    repo: &git::raw::Repository,
+    max_lines: usize,
+    writer: &mut DiffWriter<W>,
+) -> io::Result<()> {
+    for file in diff.files() {
+        let content = match file {
+            FileDiff::Modified(f) => &f.diff,
+            FileDiff::Added(f) => &f.diff,
+            FileDiff::Deleted(f) => &f.diff,
+            FileDiff::Moved(_) => {
+                writer.file_header(file)?;
+                continue;
+            }
+            // Copies are not detected when diffing.
+            FileDiff::Copied(_) => continue,
+        };
+        writer.file_header(file)?;
+
+        match content {
+            DiffContent::Plain { hunks, .. } => {
+                let lines = hunks.iter().map(|h| h.lines.len()).sum::<usize>();
+
+                if lines > max_lines {
+                    writer.note(format!("Diff too large, {lines} lines"))?;
+                    continue;
+                }
+                for hunk in hunks.iter() {
+                    writer.hunk(hunk)?;
+                }
+            }
+            DiffContent::Binary => writer.binary(file, repo)?,
+            DiffContent::Empty => writer.note("No content changes")?,
+        }
+    }
+    Ok(())
+}
+
+/// Diff the trees of two commits.
+fn tree_diff(
+    (from, to): (git::Oid, git::Oid),
+    stored: &Repository,
+    context: Option<u32>,
+) -> anyhow::Result<Diff> {
+    let repo = stored.raw();
+    let old = repo.find_commit(from.into())?.tree()?;
+    let new = repo.find_commit(to.into())?.tree()?;
+    let mut opts = git::raw::DiffOptions::new();
+    if let Some(lines) = context {
+        opts.context_lines(lines);
+    }
+    let diff = repo.diff_tree_to_tree(Some(&old), Some(&new), Some(&mut opts))?;
+
+    Ok(Diff::try_from(diff)?)
+}
+
+fn show_patch_hunks(
+    commits: (git::Oid, git::Oid),
+    stored: &Repository,
+    grep: Option<&Regex>,
+    word_diff: bool,
+    context: Option<u32>,
+    max_lines: Option<usize>,
+) -> anyhow::Result<()> {
+    let diff = tree_diff(commits, stored, context)?;
+    let mut writer = DiffWriter::new(io::stdout())
+        .styled(true)
+        .word_diff(word_diff);
+
+    if let Some(pattern) = grep {
+        if write_matching_hunks(&diff, pattern, &mut writer)? == 0 {
+            term::info!("No hunks matching `{pattern}`");
+        }
+    } else {
+        write_hunks(
+            &diff,
+            stored.raw(),
+            max_lines.unwrap_or(DEFAULT_MAX_LINES),
+            &mut writer,
+        )?;
+    }
+    Ok(())
+}
+
+fn show_patch_diff(
+    (from, to): (git::Oid, git::Oid),
+    interdiff: bool,
+    stored: &Repository,
+    grep: Option<&Regex>,
+    word_diff: bool,
+    context: Option<u32>,
+    max_lines: Option<usize>,
+) -> anyhow::Result<()> {
+    // Nb. Git can't highlight words or summarize large files the way we do, so we render
+    // the diff ourselves. Revisions may also be rebased, in which case their heads aren't
+    // related, and only their trees can be diffed.
+    if grep.is_some() || word_diff || interdiff || max_lines.is_some() {
+        return show_patch_hunks((from, to), stored, grep, word_diff, context, max_lines);
+    }
     let range = format!("{}..{}", from, to);
+    let mut args = vec![String::from("log"), String::from("--patch"), range];
+    if let Some(lines) = context {
+        args.push(format!("--unified={lines}"));
+    }
 
     process::Command::new("git")
         .current_dir(stored.path())
-        .args(["log", "--patch", &range])
+        .args(&args)
         .stdout(process::Stdio::inherit())
         .stderr(process::Stdio::inherit())
         .spawn()?
@@ -53,15 +247,92 @@ fn patch_commits(patch: &patch::Patch, stored: &Repository) -> anyhow::Result<Ve
     Ok(lines)
 }
 
+/// The events of a patch as JSON, in the order they happened.
+fn timeline_json(patch_id: &PatchId, patch: &patch::Patch) -> Vec<serde_json::Value> {
+    let mut timeline = vec![(
+        patch.timestamp(),
+        serde_json::json!({
+            "type": "opened",
+            "author": patch.author().id().to_string(),
+        }),
+    )];
+
+    for (revision_id, revision) in patch.revisions() {
+        // The first revision is part of opening the patch.
+        if **revision_id != **patch_id {
+            timeline.push((
+                revision.timestamp(),
+                serde_json::json!({
+                    "type": "updated",
+                    "author": revision.author().id().to_string(),
+                    "revision": revision_id.to_string(),
+                    "head": revision.head().to_string(),
+                }),
+            ));
+        }
+        for (nid, merge) in patch.merges().filter(|(_, m)| m.revision == *revision_id) {
+            timeline.push((
+                merge.timestamp,
+                serde_json::json!({
+                    "type": "merged",
+                    "author": Did::from(*nid).to_string(),
+                    "revision": revision_id.to_string(),
+                    "commit": merge.commit.to_string(),
+                }),
+            ));
+        }
+        for (reviewer, review) in revision.reviews() {
+            timeline.push((
+                review.timestamp(),
+                serde_json::json!({
+                    "type": "reviewed",
+                    "author": Did::from(*reviewer).to_string(),
+                    "revision": revision_id.to_string(),
+                    "verdict": review.verdict(),
+                    "summary": review.summary(),
+                }),
+            ));
+        }
+    }
+    timeline.sort_by_key(|(t, _)| *t);
+    timeline
+        .into_iter()
+        .map(|(timestamp, mut event)| {
+            event["timestamp"] = timestamp.as_secs().into();
+            event
+        })
+        .collect()
+}
+
+/// Exit code used by `--exit-status` for the given patch state.
+///
+/// Only merged patches exit successfully. The other codes start at `2`, to
+/// keep them apart from the generic failure code `1`.
+pub fn exit_code(state: &patch::State) -> i32 {
+    match state {
+        patch::State::Merged { .. } => 0,
+        patch::State::Open { .. } => 2,
+        patch::State::Draft => 3,
+        patch::State::Archived => 4,
+    }
+}
+
+/// Show a patch, returning its current state.
 pub fn run(
     patch_id: &PatchId,
     diff: bool,
+    grep: Option<&Regex>,
+    word_diff: bool,
+    context: Option<u32>,
+    max_lines: Option<usize>,
+    range: &DiffRange,
     verbose: bool,
+    json: bool,
     profile: &Profile,
     stored: &Repository,
     // TODO: Should be optional.
     workdir: &git::raw::Repository,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<patch::State> {
     let patches = patch::Patches::open(stored)?;
     let Some(patch) = patches.get(patch_id)? else {
         anyhow::bail!("Patch `{patch_id}` not found");
@@ -69,11 +340,32 @@ pub fn run(
     let (_, revision) = patch.latest();
     let state = patch.state();
     let branches = common::branches(&revision.head(), workdir)?;
-    let ahead_behind = common::ahead_behind(
-        stored.raw(),
-        *revision.head(),
-        *patch.target().head(stored)?,
-    )?;
+    let target = *patch.target().head(stored)?;
+
+    if json {
+        let (ahead, behind) = stored.raw().graph_ahead_behind(*revision.head(), target)?;
+        let mut output = serde_json::json!({
+            "id": patch_id.to_string(),
+            "title": patch.title(),
+            "author": patch.author().id().to_string(),
+            "head": revision.head().to_string(),
+            "base": revision.base().to_string(),
+            "state": state,
+            "branches": branches,
+            "ahead": ahead,
+            "behind": behind,
+            "description": patch.description(),
+            "timeline": timeline_json(patch_id, &patch),
+        });
+        if diff {
+            let commits = range.commits(patch_id, &patch, stored)?;
+            output["diff"] = serde_json::to_value(tree_diff(commits, stored, context)?)?;
+        }
+        println!("{}", serde_json::to_string_pretty(&output)?);
+
+        return Ok(state.clone());
+    }
+    let ahead_behind = common::ahead_behind(stored.raw(), *revision.head(), target)?;
 
     let mut attrs = Table::<2, term::Line>::new(TableOptions {
         spacing: 2,
@@ -145,9 +437,101 @@ pub fn run(
     widget.print();
 
     if diff {
+        let commits = range.commits(patch_id, &patch, stored)?;
+        let interdiff = matches!(range, DiffRange::Interdiff(..));
+
         term::blank();
-        show_patch_diff(&patch, stored)?;
+        show_patch_diff(
+            commits, interdiff, stored, grep, word_diff, context, max_lines,
+        )?;
         term::blank();
     }
-    Ok(())
+    Ok(state.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PATCH: &str = "\
+diff --git a/main.rs b/main.rs
+index 8e27be7..4b0f5c6 100644
+--- a/main.rs
++++ b/main.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"Hello\");
++    println!(\"Hello, world\");
+ }
+@@ -10,3 +10,3 @@
+ fn helper() {
+-    let x = 1;
++    let x = 2;
+ }
+";
+
+    #[test]
+    fn test_write_matching_hunks() {
+        let diff = git::raw::Diff::from_buffer(PATCH.as_bytes()).unwrap();
+        let diff = Diff::try_from(diff).unwrap();
+
+        let pattern = Regex::new(r"let x").unwrap();
+        let mut writer = DiffWriter::new(Vec::new());
+        let count = write_matching_hunks(&diff, &pattern, &mut writer).unwrap();
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert_eq!(count, 1);
+        assert!(output.contains("diff --git a/main.rs b/main.rs"));
+        assert!(output.contains("+    let x = 2;"));
+        assert!(!output.contains("println!"));
+
+        let pattern = Regex::new(r"^fn ").unwrap();
+        let mut writer = DiffWriter::new(Vec::new());
+        let count = write_matching_hunks(&diff, &pattern, &mut writer).unwrap();
+
+        assert_eq!(count, 2);
+
+        let pattern = Regex::new(r"unknown_symbol").unwrap();
+        let mut writer = DiffWriter::new(Vec::new());
+        let count = write_matching_hunks(&diff, &pattern, &mut writer).unwrap();
+
+        assert_eq!(count, 0);
+        assert!(writer.into_inner().is_empty());
+    }
+
+    #[test]
+    fn test_write_hunks_max_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = git::raw::Repository::init(tmp.path()).unwrap();
+        let diff = git::raw::Diff::from_buffer(PATCH.as_bytes()).unwrap();
+        let diff = Diff::try_from(diff).unwrap();
+
+        let mut writer = DiffWriter::new(Vec::new());
+        write_hunks(&diff, &repo, DEFAULT_MAX_LINES, &mut writer).unwrap();
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert!(output.contains("+    let x = 2;"));
+        assert!(!output.contains("Diff too large"));
+
+        let mut writer = DiffWriter::new(Vec::new());
+        write_hunks(&diff, &repo, 4, &mut writer).unwrap();
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert!(output.contains("diff --git a/main.rs b/main.rs"));
+        assert!(output.contains("Diff too large, 8 lines"));
+        assert!(!output.contains("let x"));
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let merged = patch::State::Merged {
+            revision: git::raw::Oid::zero().into(),
+            commit: git::raw::Oid::zero().into(),
+        };
+
+        assert_eq!(exit_code(&merged), 0);
+        assert_eq!(exit_code(&patch::State::default()), 2);
+        assert_eq!(exit_code(&patch::State::Draft), 3);
+        assert_eq!(exit_code(&patch::State::Archived), 4);
+    }
 }
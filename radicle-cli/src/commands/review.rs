@@ -1,7 +1,7 @@
 #[path = "review/builder.rs"]
 mod builder;
 #[path = "review/diff.rs"]
-mod diff;
+pub mod diff;
 
 use std::ffi::OsString;
 use std::str::FromStr;
@@ -226,7 +226,7 @@ impl<'a> ReviewBuilder<'a> {
             repo.find_commit(oid)?
         };
 
-        let mut writer = DiffWriter::new(io::stdout()).styled(true);
+        let mut writer = DiffWriter::new(io::stdout()).styled(true).word_diff(true);
         let mut queue = ReviewQueue::default(); // Queue of hunks to review.
         let mut current = None; // File of the current hunk.
         let mut stdin = io::stdin().lock();
@@ -306,6 +306,8 @@ impl<'a> ReviewBuilder<'a> {
             }
             if let Some(h) = hunk {
                 writer.hunk(h)?;
+            } else {
+                writer.binary(file, repo)?;
             }
 
             match self.prompt(&mut stdin, &mut stderr, progress) {
@@ -1,8 +1,9 @@
+use std::ops::Range;
 use std::path::Path;
 use std::{fmt, io};
 
 use radicle::git;
-use radicle_surf::diff::{FileDiff, Hunk, Modification};
+use radicle_surf::diff::{Addition, Deletion, DiffContent, FileDiff, Hunk, Modification};
 use radicle_term::ansi::Style;
 
 use crate::terminal as term;
@@ -11,6 +12,7 @@ use crate::terminal as term;
 #[derive(Default)]
 pub struct DiffWriter<W: io::Write> {
     styled: bool,
+    word_diff: bool,
     stream: W,
 }
 
@@ -20,6 +22,7 @@ impl<W: io::Write> DiffWriter<W> {
         Self {
             stream,
             styled: false,
+            word_diff: false,
         }
     }
 
@@ -34,6 +37,14 @@ impl<W: io::Write> DiffWriter<W> {
         self
     }
 
+    /// Highlight the changed words of modified lines or not.
+    ///
+    /// Only has an effect on styled output.
+    pub fn word_diff(mut self, choice: bool) -> Self {
+        self.word_diff = choice;
+        self
+    }
+
     /// Write a diff file header.
     pub fn file_header(&mut self, file: &FileDiff) -> io::Result<()> {
         fn diff(old: &Path, new: &Path) -> String {
@@ -42,13 +53,25 @@ impl<W: io::Write> DiffWriter<W> {
 
         match file {
             FileDiff::Modified(f) => {
+                let old_mode = u32::from(f.old.mode.clone());
+                let new_mode = u32::from(f.new.mode.clone());
+
                 self.meta(diff(&f.path, &f.path))?;
-                self.meta(format!(
-                    "index {}..{} {:o}",
-                    term::format::oid(f.old.oid),
-                    term::format::oid(f.new.oid),
-                    u32::from(f.new.mode.clone())
-                ))?;
+                if old_mode != new_mode {
+                    self.meta(format!("old mode {old_mode:o}"))?;
+                    self.meta(format!("new mode {new_mode:o}"))?;
+                    self.meta(format!(
+                        "index {}..{}",
+                        term::format::oid(f.old.oid),
+                        term::format::oid(f.new.oid),
+                    ))?;
+                } else {
+                    self.meta(format!(
+                        "index {}..{} {new_mode:o}",
+                        term::format::oid(f.old.oid),
+                        term::format::oid(f.new.oid),
+                    ))?;
+                }
                 self.meta(format!("--- a/{}", f.path.display()))?;
                 self.meta(format!("+++ b/{}", f.path.display()))?;
             }
@@ -90,19 +113,84 @@ impl<W: io::Write> DiffWriter<W> {
         Ok(())
     }
 
+    /// Write the note git uses in place of hunks for binary files, followed by the ids and
+    /// sizes of the old and new blobs, looked up in the given repository. Does nothing for
+    /// other files.
+    pub fn binary(&mut self, file: &FileDiff, repo: &git::raw::Repository) -> io::Result<()> {
+        let zero = git::raw::Oid::zero();
+        let (old, new, old_oid, new_oid) = match file {
+            FileDiff::Modified(f) if matches!(f.diff, DiffContent::Binary) => (
+                format!("a/{}", f.path.display()),
+                format!("b/{}", f.path.display()),
+                *f.old.oid,
+                *f.new.oid,
+            ),
+            FileDiff::Added(f) if matches!(f.diff, DiffContent::Binary) => (
+                String::from("/dev/null"),
+                format!("b/{}", f.path.display()),
+                zero,
+                *f.new.oid,
+            ),
+            FileDiff::Deleted(f) if matches!(f.diff, DiffContent::Binary) => (
+                format!("a/{}", f.path.display()),
+                String::from("/dev/null"),
+                *f.old.oid,
+                zero,
+            ),
+            _ => return Ok(()),
+        };
+        let size = |oid: git::raw::Oid| {
+            if oid.is_zero() {
+                return String::from("0 bytes");
+            }
+            repo.find_blob(oid)
+                .map(|blob| format!("{} bytes", blob.size()))
+                .unwrap_or_else(|_| String::from("unknown size"))
+        };
+
+        self.meta(format!("Binary files {old} and {new} differ"))?;
+        self.meta(format!(
+            "{} ({}) -> {} ({})",
+            term::format::oid(old_oid),
+            size(old_oid),
+            term::format::oid(new_oid),
+            size(new_oid),
+        ))
+    }
+
+    /// Write a note in place of a file's hunks, eg. when they aren't shown.
+    pub fn note(&mut self, s: impl fmt::Display) -> io::Result<()> {
+        self.meta(s)
+    }
+
     /// Write a diff hunk.
     pub fn hunk(&mut self, hunk: &Hunk<Modification>) -> io::Result<()> {
         self.magenta(hunk.header.from_utf8_lossy().trim_end())?;
 
-        for modification in &hunk.lines {
+        let mut lines = hunk.lines.iter().peekable();
+        while let Some(modification) = lines.next() {
             match modification {
-                Modification::Deletion(radicle_surf::diff::Deletion { line, .. }) => {
+                Modification::Deletion(Deletion { line, .. }) if self.word_diff => {
+                    // Pair the deletions with the additions that directly follow them.
+                    let mut deleted = vec![line.from_utf8_lossy().trim_end().to_owned()];
+                    while let Some(Modification::Deletion(Deletion { line, .. })) = lines.peek() {
+                        deleted.push(line.from_utf8_lossy().trim_end().to_owned());
+                        lines.next();
+                    }
+                    let mut added = Vec::new();
+                    while let Some(Modification::Addition(Addition { line, .. })) = lines.peek() {
+                        added.push(line.from_utf8_lossy().trim_end().to_owned());
+                        lines.next();
+                    }
+                    self.modified(&deleted, &added)?;
+                }
+                Modification::Deletion(Deletion { line, .. }) => {
                     self.deleted(format!(
                         "-{}",
                         String::from_utf8_lossy(line.as_bytes()).trim_end()
                     ))?;
                 }
-                Modification::Addition(radicle_surf::diff::Addition { line, .. }) => {
+                Modification::Addition(Addition { line, .. }) => {
                     self.added(format!("+{}", line.from_utf8_lossy()).trim_end())?;
                 }
                 Modification::Context { line, .. } => {
@@ -113,6 +201,68 @@ impl<W: io::Write> DiffWriter<W> {
         Ok(())
     }
 
+    /// Write deleted lines followed by the lines that were added in their place. If every
+    /// deleted line has a matching added line, only the changed words are highlighted,
+    /// unless the two lines have little in common.
+    fn modified(&mut self, deleted: &[String], added: &[String]) -> io::Result<()> {
+        if deleted.len() != added.len() {
+            for line in deleted {
+                self.deleted(format!("-{line}"))?;
+            }
+            for line in added {
+                self.added(format!("+{line}"))?;
+            }
+            return Ok(());
+        }
+        let spans = deleted
+            .iter()
+            .zip(added)
+            .map(|(old, new)| {
+                let (o, n) = changed_spans(old, new);
+                // Emphasizing most of a line is no more helpful than coloring all of it.
+                let unchanged = old.len() - o.len() + new.len() - n.len();
+                (unchanged * 2 >= old.len() + new.len()).then_some((o, n))
+            })
+            .collect::<Vec<_>>();
+
+        for (line, spans) in deleted.iter().zip(&spans) {
+            match spans {
+                Some((span, _)) => self.emphasized('-', line, span.clone(), term::Color::Red)?,
+                None => self.deleted(format!("-{line}"))?,
+            }
+        }
+        for (line, spans) in added.iter().zip(&spans) {
+            match spans {
+                Some((_, span)) => self.emphasized('+', line, span.clone(), term::Color::Green)?,
+                None => self.added(format!("+{line}"))?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a line, emphasizing the given span and dimming the rest.
+    fn emphasized(
+        &mut self,
+        sign: char,
+        line: &str,
+        span: Range<usize>,
+        color: term::Color,
+    ) -> io::Result<()> {
+        if !self.styled {
+            return writeln!(self.stream, "{sign}{line}");
+        }
+        let unchanged = term::Style::new(color).dim();
+        let changed = term::Style::new(color).bold();
+
+        writeln!(
+            self.stream,
+            "{}{}{}",
+            term::Paint::new(format!("{sign}{}", &line[..span.start])).with_style(unchanged),
+            term::Paint::new(&line[span.clone()]).with_style(changed),
+            term::Paint::new(&line[span.end..]).with_style(unchanged),
+        )
+    }
+
     fn write(&mut self, s: impl fmt::Display, style: Style) -> io::Result<()> {
         if self.styled {
             writeln!(self.stream, "{}", term::Paint::new(s).with_style(style))
@@ -141,3 +291,96 @@ impl<W: io::Write> DiffWriter<W> {
         self.write(s, term::Style::default().dim())
     }
 }
+
+/// Split a line into words, runs of whitespace and single punctuation characters.
+fn words(line: &str) -> Vec<&str> {
+    #[derive(PartialEq, Eq, Clone, Copy)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut previous = None;
+
+    for (i, c) in line.char_indices() {
+        let current = class(c);
+        if previous.map_or(false, |p| p != current || p == Class::Other) {
+            words.push(&line[start..i]);
+            start = i;
+        }
+        previous = Some(current);
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+    words
+}
+
+/// Get the byte ranges of the old and new line that differ, after stripping the words
+/// both lines start and end with.
+fn changed_spans(old: &str, new: &str) -> (Range<usize>, Range<usize>) {
+    let (old_words, new_words) = (words(old), words(new));
+    let prefix = old_words
+        .iter()
+        .zip(&new_words)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_words[prefix..]
+        .iter()
+        .rev()
+        .zip(new_words[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let span = |line: &str, words: &[&str]| {
+        let start = words[..prefix].iter().map(|w| w.len()).sum::<usize>();
+        let end = line.len()
+            - words[words.len() - suffix..]
+                .iter()
+                .map(|w| w.len())
+                .sum::<usize>();
+        start..end
+    };
+
+    (span(old, &old_words), span(new, &new_words))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            words("let x_1 = f(a);"),
+            vec!["let", " ", "x_1", " ", "=", " ", "f", "(", "a", ")", ";"]
+        );
+        assert!(words("").is_empty());
+    }
+
+    #[test]
+    fn test_changed_spans() {
+        let old = "let value = compute(a, b);";
+        let new = "let value = compute(a, c);";
+        let (o, n) = changed_spans(old, new);
+        assert_eq!(&old[o], "b");
+        assert_eq!(&new[n], "c");
+
+        let (o, n) = changed_spans("fn main() {", "fn main() {}");
+        assert_eq!(o, 11..11);
+        assert_eq!(n, 11..12);
+
+        let (o, n) = changed_spans("same", "same");
+        assert!(o.is_empty() && n.is_empty());
+    }
+}
@@ -39,6 +39,7 @@ Options
     --fetch, -f         Fetch from seeds
     --announce, -a      Announce refs to seeds
     --seed <nid>        Seed to fetch from (use with `--fetch`)
+    --strict            Fail if any remote fetched from a seed fails validation
     --timeout <secs>    How many seconds to wait while syncing
     --verbose, -v       Verbose output
     --help              Print help
@@ -61,6 +62,7 @@ pub struct Options {
     pub verbose: bool,
     pub timeout: time::Duration,
     pub mode: SyncMode,
+    pub strict: bool,
 }
 
 impl Args for Options {
@@ -73,6 +75,7 @@ impl Args for Options {
         let mut rid = None;
         let mut seed = None;
         let mut mode = SyncMode::default();
+        let mut strict = false;
 
         while let Some(arg) = parser.next()? {
             match arg {
@@ -90,6 +93,9 @@ impl Args for Options {
                 Long("announce") | Short('a') if mode == SyncMode::Both => {
                     mode = SyncMode::Announce;
                 }
+                Long("strict") => {
+                    strict = true;
+                }
                 Long("timeout") | Short('t') => {
                     let value = parser.value()?;
                     let secs = term::args::parse_value("timeout", value)?;
@@ -115,6 +121,7 @@ impl Args for Options {
                 timeout,
                 seed,
                 mode,
+                strict,
             },
             vec![],
         ))
@@ -137,9 +144,9 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
 
     match options.mode {
         SyncMode::Announce => announce(rid, options.timeout, node),
-        SyncMode::Fetch => fetch(rid, options.seed, &mut node, profile),
+        SyncMode::Fetch => fetch(rid, options.seed, options.strict, &mut node, profile),
         SyncMode::Both => {
-            fetch(rid, options.seed, &mut node, profile)?;
+            fetch(rid, options.seed, options.strict, &mut node, profile)?;
             announce(rid, options.timeout, node)?;
 
             Ok(())
@@ -178,9 +185,12 @@ fn announce(rid: Id, timeout: time::Duration, mut node: Node) -> anyhow::Result<
     Ok(())
 }
 
+/// Fetch a repository from the given seed, or all connected seeds. In strict mode, a seed
+/// with remotes that fail validation counts as failed, and any failure is an error.
 pub fn fetch(
     rid: Id,
     seed: Option<NodeId>,
+    strict: bool,
     node: &mut Node,
     profile: Profile,
 ) -> anyhow::Result<()> {
@@ -189,10 +199,10 @@ pub fn fetch(
     }
 
     let results = if let Some(seed) = seed {
-        let result = fetch_from(rid, &seed, node)?;
+        let result = fetch_from(rid, &seed, strict, node)?;
         FetchResults::from(vec![(seed, result)])
     } else {
-        fetch_all(rid, node)?
+        fetch_all(rid, strict, node)?
     };
     let success = results.success().count();
     let failed = results.failed().count();
@@ -202,10 +212,13 @@ pub fn fetch(
     } else {
         term::success!("Fetched repository from {success} seed(s)");
     }
+    if strict && failed > 0 {
+        anyhow::bail!("failed to fetch repository from {failed} seed(s)");
+    }
     Ok(())
 }
 
-pub fn fetch_all(rid: Id, node: &mut Node) -> Result<FetchResults, node::Error> {
+pub fn fetch_all(rid: Id, strict: bool, node: &mut Node) -> Result<FetchResults, node::Error> {
     // Get seeds. This consults the local routing table only.
     let seeds = node.seeds(rid)?;
     let mut results = FetchResults::default();
@@ -213,24 +226,41 @@ pub fn fetch_all(rid: Id, node: &mut Node) -> Result<FetchResults, node::Error>
     if seeds.has_connections() {
         // Fetch from all seeds.
         for seed in seeds.connected() {
-            let result = fetch_from(rid, seed, node)?;
+            let result = fetch_from(rid, seed, strict, node)?;
             results.push(*seed, result);
         }
     }
     Ok(results)
 }
 
-pub fn fetch_from(rid: Id, seed: &NodeId, node: &mut Node) -> Result<FetchResult, node::Error> {
+pub fn fetch_from(
+    rid: Id,
+    seed: &NodeId,
+    strict: bool,
+    node: &mut Node,
+) -> Result<FetchResult, node::Error> {
     let spinner = term::spinner(format!(
         "Fetching {} from {}..",
         term::format::tertiary(rid),
         term::format::tertiary(term::format::node(seed))
     ));
-    let result = node.fetch(rid, *seed)?;
+    let mut result = node.fetch(rid, *seed)?;
+
+    if strict {
+        result = result.into_strict();
+    }
 
     match &result {
         FetchResult::Success { .. } => {
             spinner.finish();
+
+            for validation in result.errors() {
+                term::warning(&format!(
+                    "Skipped {}: {}",
+                    term::format::node(&validation.remote),
+                    validation.reason
+                ));
+            }
         }
         FetchResult::Failed { reason } => {
             spinner.error(reason);
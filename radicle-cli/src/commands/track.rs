@@ -123,7 +123,7 @@ pub fn run(options: Options, ctx: impl term::Context) -> anyhow::Result<()> {
             track_repo(rid, scope, &mut node)?;
 
             if options.fetch {
-                sync::fetch(rid, None, &mut node, profile)?;
+                sync::fetch(rid, None, false, &mut node, profile)?;
             }
         }
     }
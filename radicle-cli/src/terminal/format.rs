@@ -84,6 +84,26 @@ pub fn timestamp(time: &Timestamp) -> Paint<String> {
     Paint::new(fmt.convert(duration))
 }
 
+/// Format a size in bytes, using binary units, eg. `4.5 KiB`.
+pub fn bytes(size: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if size < 1024 {
+        return format!("{size} B");
+    }
+    let mut size = size as f64 / 1024.;
+    let mut unit = UNITS[0];
+
+    for u in &UNITS[1..] {
+        if size < 1024. {
+            break;
+        }
+        size /= 1024.;
+        unit = u;
+    }
+    format!("{size:.1} {unit}")
+}
+
 /// Identity formatter that takes a profile and displays it as
 /// `<node-id> (<username>)` depending on the configuration.
 pub struct Identity<'a> {
@@ -229,6 +249,15 @@ impl<'a> IntoIterator for Author<'a> {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1024), "1.0 KiB");
+        assert_eq!(bytes(4608), "4.5 KiB");
+        assert_eq!(bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
     #[test]
     fn test_strip_comments() {
         let test = "\
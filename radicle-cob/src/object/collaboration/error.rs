@@ -47,6 +47,8 @@ pub enum Update {
     NoSuchObject,
     #[error(transparent)]
     CreateChange(#[from] git::change::error::Create),
+    #[error(transparent)]
+    LoadChange(#[from] git::change::error::Load),
     #[error("failed to get references during object update")]
     Refs {
         #[source]
@@ -58,4 +60,9 @@ pub enum Update {
     Io(#[from] std::io::Error),
     #[error("signer must belong to the author")]
     SignerIsNotAuthor,
+    #[error("object head is not {expected}, it was updated concurrently")]
+    Stale {
+        expected: git_ext::Oid,
+        actual: Vec<git_ext::Oid>,
+    },
 }
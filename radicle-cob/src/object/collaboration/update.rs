@@ -12,6 +12,10 @@ use super::error;
 pub struct Updated {
     /// The new head commit of the DAG.
     pub head: Oid,
+    /// The parents of the new head, as stored, ie. the previous tips of the object,
+    /// followed by the additional parents passed to [`update`]. The resource is not
+    /// included.
+    pub parents: Vec<Oid>,
     /// The newly updated collaborative object.
     pub object: CollaborativeObject,
 }
@@ -28,6 +32,11 @@ pub struct Update {
     pub typename: TypeName,
     /// The message to add when updating this object.
     pub message: String,
+    /// The head the update is based on, if known. If the object's tips differ from it,
+    /// the update is rejected as stale. Nb. An object with more than one tip, ie. with
+    /// concurrent changes that weren't merged yet, never has a single head, so updates
+    /// to it are always stale. Updating it without an expected head merges its tips.
+    pub expected_head: Option<Oid>,
 }
 
 /// Update an existing [`CollaborativeObject`].
@@ -67,6 +76,7 @@ where
         history_type,
         changes,
         message,
+        expected_head,
     } = args;
 
     let existing_refs = storage
@@ -77,6 +87,17 @@ where
         .map(|graph| graph.evaluate())
         .ok_or(error::Update::NoSuchObject)?;
 
+    if let Some(expected) = expected_head {
+        let actual = object.tips();
+
+        if actual.len() != 1 || !actual.contains(&expected) {
+            return Err(error::Update::Stale {
+                expected,
+                actual: actual.into_iter().collect(),
+            });
+        }
+    }
+
     let change = storage.store(
         resource,
         parents,
@@ -94,6 +115,12 @@ where
         .update(identifier, typename, &object_id, &change)
         .map_err(|err| error::Update::Refs { err: Box::new(err) })?;
 
+    let parents = storage
+        .parents_of(&change.id)?
+        .into_iter()
+        .filter(|p| *p != resource)
+        .collect();
+
     object.history.extend(
         change.id,
         change.signature.key,
@@ -105,5 +132,6 @@ where
     Ok(Updated {
         object,
         head: change.id,
+        parents,
     })
 }
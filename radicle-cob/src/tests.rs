@@ -7,8 +7,8 @@ use qcheck::Arbitrary;
 use radicle_crypto::Signer;
 
 use crate::{
-    create, get, list, object, test::arbitrary::Invalid, update, Create, ObjectId, TypeName,
-    Update, Updated,
+    create, get, list, object, object::collaboration::error, test::arbitrary::Invalid, update,
+    Create, ObjectId, TypeName, Update, Updated,
 };
 
 use super::test;
@@ -126,7 +126,9 @@ fn update_cob() {
         .unwrap()
         .expect("BUG: cob was missing");
 
-    let Updated { object, .. } = update(
+    let Updated {
+        object, parents, ..
+    } = update(
         &storage,
         &signer,
         proj.project.content_id,
@@ -138,6 +140,7 @@ fn update_cob() {
             object_id: *cob.id(),
             typename: typename.clone(),
             message: "commenting xyz.rad.issue".to_string(),
+            expected_head: None,
         },
     )
     .unwrap();
@@ -148,6 +151,151 @@ fn update_cob() {
 
     assert_ne!(object, not_expected);
     assert_eq!(object, expected);
+    assert_eq!(
+        parents,
+        not_expected
+            .history()
+            .tips()
+            .into_iter()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn update_cob_stale() {
+    let storage = test::Storage::new();
+    let signer = gen::<MockSigner>(1);
+    let terry = test::Person::new(&storage, "terry", *signer.public_key()).unwrap();
+    let proj = test::Project::new(&storage, "discworld", *signer.public_key()).unwrap();
+    let proj = test::RemoteProject {
+        project: proj,
+        person: terry,
+    };
+    let typename = "xyz.rad.issue".parse::<TypeName>().unwrap();
+    let cob = create(
+        &storage,
+        &signer,
+        proj.project.content_id,
+        vec![],
+        &proj.identifier(),
+        Create {
+            history_type: "test".to_string(),
+            contents: nonempty!(Vec::new()),
+            typename: typename.clone(),
+            message: "creating xyz.rad.issue".to_string(),
+        },
+    )
+    .unwrap();
+    let comment = |expected_head| {
+        update(
+            &storage,
+            &signer,
+            proj.project.content_id,
+            vec![],
+            &proj.identifier(),
+            Update {
+                changes: nonempty!(b"issue 1".to_vec()),
+                history_type: "test".to_string(),
+                object_id: *cob.id(),
+                typename: typename.clone(),
+                message: "commenting xyz.rad.issue".to_string(),
+                expected_head: Some(expected_head),
+            },
+        )
+    };
+
+    let Updated { head, .. } = comment(**cob.id()).unwrap();
+
+    // The object was updated since we last saw it.
+    match comment(**cob.id()) {
+        Err(error::Update::Stale { expected, actual }) => {
+            assert_eq!(expected, **cob.id());
+            assert_eq!(actual, vec![head]);
+        }
+        result => panic!("expected stale update, got {result:?}"),
+    }
+    assert!(comment(head).is_ok());
+}
+
+#[test]
+fn update_cob_stale_multiple_tips() {
+    let storage = test::Storage::new();
+    let signer = gen::<MockSigner>(1);
+    let terry = test::Person::new(&storage, "terry", *signer.public_key()).unwrap();
+    let rincewind = test::Person::new(&storage, "rincewind", *signer.public_key()).unwrap();
+    let proj = test::Project::new(&storage, "discworld", *signer.public_key()).unwrap();
+    let rincewind = test::RemoteProject {
+        project: proj.clone(),
+        person: rincewind,
+    };
+    let proj = test::RemoteProject {
+        project: proj,
+        person: terry,
+    };
+    let typename = "xyz.rad.issue".parse::<TypeName>().unwrap();
+    let cob = create(
+        &storage,
+        &signer,
+        proj.project.content_id,
+        vec![],
+        &proj.identifier(),
+        Create {
+            history_type: "test".to_string(),
+            contents: nonempty!(Vec::new()),
+            typename: typename.clone(),
+            message: "creating xyz.rad.issue".to_string(),
+        },
+    )
+    .unwrap();
+    let comment = |remote: &test::RemoteProject, expected_head| {
+        update(
+            &storage,
+            &signer,
+            proj.project.content_id,
+            vec![],
+            &remote.identifier(),
+            Update {
+                changes: nonempty!(b"issue 1".to_vec()),
+                history_type: "test".to_string(),
+                object_id: *cob.id(),
+                typename: typename.clone(),
+                message: "commenting xyz.rad.issue".to_string(),
+                expected_head,
+            },
+        )
+    };
+    let point = |remote: &test::RemoteProject, oid: git_ext::Oid| {
+        let name = format!(
+            "refs/rad/{}/cobs/{typename}/{}",
+            remote.identifier().to_path(),
+            cob.id()
+        );
+        storage
+            .as_raw()
+            .reference(&name, oid.into(), true, "")
+            .unwrap();
+    };
+
+    // Both remotes comment on the initial change, concurrently.
+    let Updated { head: a, .. } = comment(&proj, None).unwrap();
+    point(&proj, **cob.id());
+    let Updated { head: b, .. } = comment(&rincewind, None).unwrap();
+    point(&proj, a);
+
+    for expected in [a, b] {
+        match comment(&proj, Some(expected)) {
+            Err(error::Update::Stale { actual, .. }) => {
+                assert_eq!(actual.len(), 2);
+                assert!(actual.contains(&a));
+                assert!(actual.contains(&b));
+            }
+            result => panic!("expected stale update, got {result:?}"),
+        }
+    }
+    // Updating without an expected head merges the tips.
+    let Updated { head, parents, .. } = comment(&proj, None).unwrap();
+    assert_eq!(parents.len(), 2);
+    assert!(comment(&proj, Some(head)).is_ok());
 }
 
 #[test]
@@ -202,6 +350,7 @@ fn traverse_cobs() {
             object_id: *cob.id(),
             typename,
             message: "commenting on xyz.rad.issue".to_string(),
+            expected_head: None,
         },
     )
     .unwrap();
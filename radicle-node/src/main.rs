@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::{env, fs, net, process};
 
 use anyhow::anyhow;
@@ -11,7 +12,7 @@ use radicle::profile;
 use radicle_node::crypto::ssh::keystore::{Keystore, MemorySigner};
 use radicle_node::prelude::{Address, NodeId};
 use radicle_node::Runtime;
-use radicle_node::{logger, service, signals};
+use radicle_node::{logger, service, signals, worker};
 
 pub const HELP_MSG: &str = r#"
 Usage
@@ -23,6 +24,10 @@ Options
     --connect            <peer>         Connect to the given peer address on start
     --external-address   <address>      Publicly accessible address (default 0.0.0.0:8776)
     --git-daemon         <address>      Address to bind git-daemon to (default 0.0.0.0:9418)
+    --git-binary         <path>         Path to the git binary to use (default git)
+    --upload-host        <host>         Only serve git requests made for this host, or
+                                        through a peer's local tunnel
+    --channel-capacity   <count>        Git data chunks a fetch can queue, 0 for unbounded (default 256)
     --tracking-policy    (track|block)  Default tracking policy
     --tracking-scope     (trusted|all)  Default scope for tracking policies
     --force                             Force start even if an existing control socket is found
@@ -33,6 +38,9 @@ Options
 #[derive(Debug)]
 struct Options {
     daemon: Option<net::SocketAddr>,
+    git_binary: PathBuf,
+    upload_host: Option<String>,
+    channel_capacity: Option<usize>,
     listen: Vec<net::SocketAddr>,
     force: bool,
 }
@@ -44,6 +52,9 @@ impl Options {
         let mut parser = lexopt::Parser::from_env();
         let mut listen = Vec::new();
         let mut daemon = None;
+        let mut git_binary = PathBuf::from("git");
+        let mut upload_host = None;
+        let mut channel_capacity = Some(worker::DEFAULT_CHANNEL_CAPACITY);
         let mut force = false;
 
         while let Some(arg) = parser.next()? {
@@ -63,6 +74,16 @@ impl Options {
                     let addr = parser.value()?.parse()?;
                     daemon = Some(addr);
                 }
+                Long("git-binary") => {
+                    git_binary = parser.value()?.into();
+                }
+                Long("upload-host") => {
+                    upload_host = Some(parser.value()?.parse()?);
+                }
+                Long("channel-capacity") => {
+                    let capacity: usize = parser.value()?.parse()?;
+                    channel_capacity = (capacity > 0).then_some(capacity);
+                }
                 Long("tracking-policy") => {
                     let policy = parser
                         .value()?
@@ -108,6 +129,9 @@ impl Options {
 
         Ok(Self {
             daemon,
+            git_binary,
+            upload_host,
+            channel_capacity,
             force,
             listen,
         })
@@ -144,7 +168,19 @@ fn execute() -> anyhow::Result<()> {
         log::debug!(target: "node", "Removing existing control socket..");
         fs::remove_file(home.socket()).ok();
     }
-    Runtime::init(home, config, options.listen, proxy, daemon, signals, signer)?.run()?;
+    Runtime::init(
+        home,
+        config,
+        options.listen,
+        proxy,
+        daemon,
+        options.git_binary,
+        options.upload_host,
+        options.channel_capacity,
+        signals,
+        signer,
+    )?
+    .run()?;
 
     Ok(())
 }
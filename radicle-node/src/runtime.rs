@@ -5,7 +5,7 @@ use std::io::{BufRead, BufReader};
 use std::os::unix::net::UnixListener;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::{fs, io, net, time};
+use std::{fs, io, net};
 
 use crossbeam_channel as chan;
 use cyphernet::Ecdh;
@@ -106,6 +106,7 @@ pub struct Runtime {
     pub storage: Storage,
     pub reactor: Reactor<wire::Control, popol::Poller>,
     pub daemon: net::SocketAddr,
+    pub git_binary: PathBuf,
     pub pool: worker::Pool,
     pub local_addrs: Vec<net::SocketAddr>,
     pub signals: chan::Receiver<()>,
@@ -115,12 +116,16 @@ impl Runtime {
     /// Initialize the runtime.
     ///
     /// This function spawns threads.
+    #[allow(clippy::too_many_arguments)]
     pub fn init<G: Signer + Ecdh + 'static>(
         home: Home,
         config: service::Config,
         listen: Vec<net::SocketAddr>,
         proxy: net::SocketAddr,
         daemon: net::SocketAddr,
+        git_binary: PathBuf,
+        upload_host: Option<String>,
+        channel_capacity: Option<usize>,
         signals: chan::Receiver<()>,
         signer: G,
     ) -> Result<Runtime, Error>
@@ -191,7 +196,7 @@ impl Runtime {
         );
 
         let (worker_send, worker_recv) = chan::unbounded::<worker::Task>();
-        let mut wire = Wire::new(service, worker_send, signer, proxy, clock);
+        let mut wire = Wire::new(service, worker_send, signer, proxy, channel_capacity, clock);
         let mut local_addrs = Vec::new();
 
         for addr in listen {
@@ -205,7 +210,7 @@ impl Runtime {
         }
         let reactor = Reactor::named(wire, popol::Poller::new(), thread::name(&id, "service"))?;
         let handle = Handle::new(home.clone(), reactor.controller(), emitter);
-        let atomic = git::version()? >= git::VERSION_REQUIRED;
+        let atomic = git::version_of(&git_binary)? >= git::VERSION_REQUIRED;
 
         if !atomic {
             log::warn!(
@@ -220,10 +225,14 @@ impl Runtime {
             handle.clone(),
             worker::Config {
                 capacity: 8,
-                timeout: time::Duration::from_secs(9),
+                handshake_timeout: wire::DEFAULT_CHANNEL_TIMEOUT,
+                transfer_timeout: wire::DEFAULT_TRANSFER_TIMEOUT,
                 storage: storage.clone(),
                 daemon,
                 atomic,
+                git_binary: git_binary.clone(),
+                upload_host,
+                ls_refs_batch_size: worker::DEFAULT_LS_REFS_BATCH_SIZE,
             },
         );
         let control = match UnixListener::bind(home.socket()) {
@@ -243,6 +252,7 @@ impl Runtime {
             storage,
             reactor,
             daemon,
+            git_binary,
             handle,
             pool,
             signals,
@@ -269,7 +279,7 @@ impl Runtime {
 
         log::info!(target: "node", "Spawning git daemon at {}..", self.storage.path().display());
 
-        let mut daemon = daemon::spawn(self.storage.path(), self.daemon)?;
+        let mut daemon = daemon::spawn(&self.git_binary, self.storage.path(), self.daemon)?;
         thread::spawn(&self.id, "daemon", {
             let stderr = daemon.stderr.take().unwrap();
             || {
@@ -320,11 +330,11 @@ pub mod daemon {
     }
 
     /// Spawn the daemon process.
-    pub fn spawn(storage: &Path, addr: net::SocketAddr) -> io::Result<Child> {
+    pub fn spawn(git: &Path, storage: &Path, addr: net::SocketAddr) -> io::Result<Child> {
         let storage = storage.canonicalize()?;
         let listen = format!("--listen={}", addr.ip());
         let port = format!("--port={}", addr.port());
-        let child = Command::new("git")
+        let child = Command::new(git)
             .env_clear()
             .envs(env::vars().filter(|(k, _)| k == "PATH" || k.starts_with("GIT")))
             .envs(radicle::git::env::GIT_DEFAULT_CONFIG)
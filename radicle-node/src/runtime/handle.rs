@@ -1,6 +1,8 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::os::unix::net::UnixStream;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{fmt, io, time};
 
 use crossbeam_channel as chan;
@@ -17,10 +19,15 @@ use crate::service::tracking;
 use crate::service::NodeId;
 use crate::service::{CommandError, QueryState};
 use crate::service::{Event, Events};
+use crate::storage::RefUpdate;
 use crate::wire;
 use crate::wire::StreamId;
+use crate::worker;
 use crate::worker::TaskResult;
 
+/// Window during which identical ref updates from workers are only reported once.
+pub const REF_UPDATES_WINDOW: time::Duration = time::Duration::from_millis(500);
+
 /// An error resulting from a handle method.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -67,6 +74,8 @@ pub struct Handle {
     shutdown: Arc<AtomicBool>,
     /// Publishes events to subscribers.
     emitter: Emitter<Event>,
+    /// Ref updates recently reported by workers.
+    updates: Arc<Mutex<RecentUpdates>>,
 }
 
 impl Handle {
@@ -89,6 +98,7 @@ impl Clone for Handle {
             controller: self.controller.clone(),
             shutdown: self.shutdown.clone(),
             emitter: self.emitter.clone(),
+            updates: self.updates.clone(),
         }
     }
 }
@@ -104,10 +114,23 @@ impl Handle {
             controller,
             shutdown: Arc::default(),
             emitter,
+            updates: Arc::new(Mutex::new(RecentUpdates::new(REF_UPDATES_WINDOW))),
         }
     }
 
-    pub fn worker_result(&mut self, result: TaskResult) -> Result<(), io::Error> {
+    pub fn worker_result(&mut self, mut result: TaskResult) -> Result<(), io::Error> {
+        // Coalesce ref updates that were already reported by another worker, so that the
+        // same change isn't announced more than once.
+        if let worker::FetchResult::Initiator {
+            rid,
+            result: Ok((updated, _, _)),
+        } = &mut result.result
+        {
+            self.updates
+                .lock()
+                .unwrap()
+                .dedup(*rid, updated, time::Instant::now());
+        }
         self.controller.cmd(wire::Control::Worker(result))
     }
 
@@ -120,6 +143,39 @@ impl Handle {
     }
 }
 
+/// Ref updates recently reported by workers, used to coalesce identical updates.
+#[derive(Debug)]
+struct RecentUpdates {
+    /// How long an update is remembered for.
+    window: time::Duration,
+    /// Updates seen, with the time they were first seen.
+    seen: HashMap<(Id, RefUpdate), time::Instant>,
+}
+
+impl RecentUpdates {
+    fn new(window: time::Duration) -> Self {
+        Self {
+            window,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Remove the updates that were already seen within the window, and remember the others.
+    fn dedup(&mut self, rid: Id, updates: &mut Vec<RefUpdate>, now: time::Instant) {
+        let window = self.window;
+
+        self.seen
+            .retain(|_, seen| now.saturating_duration_since(*seen) < window);
+        updates.retain(|update| match self.seen.entry((rid, update.clone())) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(e) => {
+                e.insert(now);
+                true
+            }
+        });
+    }
+}
+
 impl radicle::node::Handle for Handle {
     type Sessions = Vec<radicle::node::Session>;
     type Error = Error;
@@ -253,3 +309,41 @@ impl radicle::node::Handle for Handle {
             .map_err(|_| Error::ChannelDisconnected)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::git;
+    use crate::test::arbitrary;
+
+    #[test]
+    fn test_recent_updates_dedup() {
+        let rid = arbitrary::gen::<Id>(1);
+        let update = RefUpdate::Created {
+            name: git::refname!("refs/heads/master"),
+            oid: arbitrary::oid(),
+        };
+        let window = time::Duration::from_millis(100);
+        let mut recent = RecentUpdates::new(window);
+        let now = time::Instant::now();
+
+        let mut first = vec![update.clone()];
+        recent.dedup(rid, &mut first, now);
+        assert_eq!(first, vec![update.clone()]);
+
+        let mut second = vec![update.clone()];
+        recent.dedup(rid, &mut second, now + window / 2);
+        assert!(
+            second.is_empty(),
+            "Identical update within the window is dropped"
+        );
+
+        let mut third = vec![update.clone()];
+        recent.dedup(rid, &mut third, now + window * 2);
+        assert_eq!(
+            third,
+            vec![update],
+            "Update is reported again after the window"
+        );
+    }
+}
@@ -34,9 +34,9 @@ use crate::service::message::{Announcement, AnnouncementMessage, Ping};
 use crate::service::message::{NodeAnnouncement, RefsAnnouncement};
 use crate::service::tracking::{store::Write, Scope};
 use crate::storage;
+use crate::storage::ReadRepository;
 use crate::storage::{Namespaces, ReadStorage};
-use crate::storage::{ReadRepository, RefUpdate};
-use crate::worker::FetchError;
+use crate::worker::{FetchError, Fetched};
 use crate::Link;
 
 pub use crate::node::events::{Event, Events};
@@ -594,9 +594,15 @@ where
             session::FetchResult::Ready => {
                 debug!(target: "service", "Fetch initiated for {rid} with {seed}..");
 
-                match self.tracking.namespaces_for(&self.storage, &rid) {
-                    Ok(namespaces) => {
-                        self.outbox.fetch(session, rid, namespaces);
+                let namespaces = self.tracking.namespaces_for(&self.storage, &rid);
+                let blocked = self
+                    .tracking
+                    .blocked_nodes()
+                    .map_err(|err| NamespacesError::FailedNodes { rid, err });
+
+                match namespaces.and_then(|namespaces| Ok((namespaces, blocked?))) {
+                    Ok((namespaces, blocked)) => {
+                        self.outbox.fetch(session, rid, namespaces, blocked);
                     }
                     Err(err) => {
                         error!(target: "service", "Error getting namespaces for {rid}: {err}");
@@ -619,14 +625,9 @@ where
         }
     }
 
-    pub fn fetched(
-        &mut self,
-        rid: Id,
-        remote: NodeId,
-        result: Result<(Vec<RefUpdate>, HashSet<NodeId>), FetchError>,
-    ) {
+    pub fn fetched(&mut self, rid: Id, remote: NodeId, result: Result<Fetched, FetchError>) {
         let result = match result {
-            Ok((updated, namespaces)) => {
+            Ok((updated, namespaces, validations)) => {
                 debug!(target: "service", "Fetched {rid} from {remote} successfully");
 
                 for update in &updated {
@@ -641,6 +642,7 @@ where
                 FetchResult::Success {
                     updated,
                     namespaces,
+                    validations,
                 }
             }
             Err(err) => {
@@ -674,6 +676,7 @@ where
                 FetchResult::Success {
                     updated,
                     namespaces,
+                    ..
                 } if !updated.is_empty() => {
                     if let Err(e) = self.announce_refs(rid, namespaces) {
                         error!(target: "service", "Failed to announce new refs: {e}");
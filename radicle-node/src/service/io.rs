@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use log::*;
 
@@ -26,6 +26,8 @@ pub enum Io {
         remote: NodeId,
         /// Namespaces being fetched.
         namespaces: Namespaces,
+        /// Nodes whose refs shouldn't be stored, even if they're in the namespaces.
+        blocked: HashSet<NodeId>,
     },
     /// Ask for a wakeup in a specified amount of time.
     Wakeup(LocalDuration),
@@ -77,10 +79,17 @@ impl Outbox {
         self.io.push_back(Io::Wakeup(after));
     }
 
-    pub fn fetch(&mut self, remote: &mut Session, rid: Id, namespaces: Namespaces) {
+    pub fn fetch(
+        &mut self,
+        remote: &mut Session,
+        rid: Id,
+        namespaces: Namespaces,
+        blocked: HashSet<NodeId>,
+    ) {
         self.io.push_back(Io::Fetch {
             rid,
             namespaces,
+            blocked,
             remote: remote.id,
         });
     }
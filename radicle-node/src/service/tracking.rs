@@ -107,6 +107,15 @@ impl<T> Config<T> {
         }))
     }
 
+    /// Get the nodes that are blocked. Their refs are never stored, whatever the scope of
+    /// the repository being fetched.
+    pub fn blocked_nodes(&self) -> Result<HashSet<NodeId>, Error> {
+        Ok(self
+            .node_policies()?
+            .filter_map(|node| (node.policy == Policy::Block).then_some(node.id))
+            .collect())
+    }
+
     pub fn namespaces_for<S>(&self, storage: &S, rid: &Id) -> Result<Namespaces, NamespacesError>
     where
         S: ReadStorage,
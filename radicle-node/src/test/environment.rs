@@ -34,7 +34,7 @@ use radicle::Storage;
 use crate::node::NodeId;
 use crate::service::Event;
 use crate::storage::git::transport;
-use crate::{runtime, runtime::Handle, service, Runtime};
+use crate::{runtime, runtime::Handle, service, worker, Runtime};
 
 pub use service::Config;
 
@@ -348,6 +348,9 @@ impl<G: cyphernet::Ecdh<Pk = NodeId> + Signer + Clone> Node<G> {
             listen,
             proxy,
             daemon,
+            PathBuf::from("git"),
+            None,
+            Some(worker::DEFAULT_CHANNEL_CAPACITY),
             signals,
             self.signer.clone(),
         )
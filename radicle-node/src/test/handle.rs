@@ -40,6 +40,7 @@ impl radicle::node::Handle for Handle {
         Ok(FetchResult::Success {
             updated: vec![],
             namespaces: HashSet::new(),
+            validations: vec![],
         })
     }
 
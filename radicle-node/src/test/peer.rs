@@ -387,6 +387,7 @@ where
                 rid,
                 remote,
                 namespaces,
+                ..
             } = io
             {
                 Some((rid, remote, namespaces))
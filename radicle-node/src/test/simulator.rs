@@ -21,7 +21,7 @@ use crate::service::{DisconnectReason, Event, Message, NodeId};
 use crate::storage::{Namespaces, RefUpdate};
 use crate::storage::{WriteRepository, WriteStorage};
 use crate::test::peer::Service;
-use crate::worker::FetchError;
+use crate::worker::{FetchError, Fetched};
 use crate::Link;
 
 /// Minimum latency between peers.
@@ -66,11 +66,7 @@ pub enum Input {
     /// Received a message from a remote peer.
     Received(NodeId, Vec<Message>),
     /// Fetch completed for a node.
-    Fetched(
-        Id,
-        NodeId,
-        Rc<Result<(Vec<RefUpdate>, HashSet<NodeId>), FetchError>>,
-    ),
+    Fetched(Id, NodeId, Rc<Result<Fetched, FetchError>>),
     /// Used to advance the state machine after some wall time has passed.
     Wake,
 }
@@ -419,7 +415,7 @@ impl<S: WriteStorage + 'static, G: Signer> Simulation<S, G> {
                             Err(e) => panic!("Failed to open repository: {e}"),
                         };
                         match &result {
-                            Ok((_, remotes)) => {
+                            Ok((_, remotes, _)) => {
                                 fetch(&mut repo, &nid, Namespaces::Trusted(remotes.clone()))
                                     .unwrap();
                             }
@@ -619,6 +615,7 @@ impl<S: WriteStorage + 'static, G: Signer> Simulation<S, G> {
                 rid,
                 remote,
                 namespaces,
+                ..
             } => {
                 log::info!(
                     target: "sim",
@@ -654,6 +651,7 @@ impl<S: WriteStorage + 'static, G: Signer> Simulation<S, G> {
                                         Namespaces::Trusted(hs) => hs,
                                         Namespaces::All => HashSet::new(),
                                     },
+                                    vec![],
                                 ))),
                             ),
                         },
@@ -1166,14 +1166,14 @@ fn test_queued_fetch() {
     alice.elapse(KEEP_ALIVE_DELTA);
 
     // Finish the 1st fetch.
-    alice.fetched(rid1, bob.id, Ok((vec![], Default::default())));
+    alice.fetched(rid1, bob.id, Ok((vec![], Default::default(), vec![])));
     // Now the 1st fetch is done, the 2nd fetch is dequeued.
     assert_matches!(alice.fetches().next(), Some((rid, _, _)) if rid == rid2);
     // ... but not the third.
     assert_matches!(alice.fetches().next(), None);
 
     // Finish the 2nd fetch.
-    alice.fetched(rid2, bob.id, Ok((vec![], Default::default())));
+    alice.fetched(rid2, bob.id, Ok((vec![], Default::default(), vec![])));
     // Now the 2nd fetch is done, the 3rd fetch is dequeued.
     assert_matches!(alice.fetches().next(), Some((rid, _, _)) if rid == rid3);
 }
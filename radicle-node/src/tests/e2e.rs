@@ -2,7 +2,8 @@ use std::{collections::HashSet, thread, time};
 
 use radicle::crypto::{test::signer::MockSigner, Signer};
 use radicle::git;
-use radicle::node::{Alias, FetchResult, Handle as _};
+use radicle::node::tracking::{self, Policy};
+use radicle::node::{Alias, FetchResult, Handle as _, TRACKING_DB_FILE};
 use radicle::storage::{ReadRepository, ReadStorage, WriteRepository, WriteStorage};
 use radicle::test::fixtures;
 use radicle::{assert_matches, rad};
@@ -417,6 +418,57 @@ fn test_fetch_trusted_remotes() {
     assert!(bob_remotes.contains(&alice.id));
 }
 
+#[test]
+//
+//     alice -- bob
+//
+fn test_fetch_all_except_blocked() {
+    logger::init(log::Level::Debug);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let mut alice = Node::init(tmp.path(), Config::new(Alias::new("alice")));
+    let bob = Node::init(tmp.path(), Config::new(Alias::new("bob")));
+    let acme = alice.project("acme", "");
+    let carol = MockSigner::default();
+    let dave = MockSigner::default();
+
+    rad::fork_remote(acme, &alice.id, &carol, &alice.storage).unwrap();
+    rad::fork_remote(acme, &alice.id, &dave, &alice.storage).unwrap();
+
+    // Bob blocks Carol, but doesn't know about Dave.
+    tracking::store::Config::open(bob.home.node().join(TRACKING_DB_FILE))
+        .unwrap()
+        .set_node_policy(carol.public_key(), Policy::Block)
+        .unwrap();
+
+    let mut alice = alice.spawn();
+    let mut bob = bob.spawn();
+
+    alice.connect(&bob);
+    converge([&alice, &bob]);
+
+    assert!(bob.handle.track_repo(acme, Scope::All).unwrap());
+
+    // Nb. The first fetch is a clone, which only fetches the delegates.
+    for _ in 0..2 {
+        let result = bob.handle.fetch(acme, alice.id).unwrap();
+        assert!(result.is_success());
+    }
+
+    let bob_remotes = bob
+        .storage
+        .repository(acme)
+        .unwrap()
+        .remote_ids()
+        .unwrap()
+        .collect::<Result<HashSet<_>, _>>()
+        .unwrap();
+
+    assert!(bob_remotes.contains(&alice.id));
+    assert!(bob_remotes.contains(dave.public_key()));
+    assert!(!bob_remotes.contains(carol.public_key()));
+}
+
 #[test]
 fn test_missing_remote() {
     logger::init(log::Level::Debug);
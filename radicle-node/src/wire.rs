@@ -6,6 +6,7 @@ mod varint;
 pub use frame::StreamId;
 pub use message::{AddressType, MessageType};
 pub use protocol::{Control, Wire, WireReader, WireSession, WireWriter};
+pub use protocol::{DEFAULT_CHANNEL_TIMEOUT, DEFAULT_TRANSFER_TIMEOUT};
 
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
@@ -46,6 +46,8 @@ pub const NOISE_XK: HandshakePattern = HandshakePattern {
 /// Default time to wait to receive something from a worker channel. Applies to
 /// workers waiting for data from remotes as well.
 pub const DEFAULT_CHANNEL_TIMEOUT: time::Duration = time::Duration::from_secs(9);
+/// Default time to wait for more data from a worker channel, once data started coming in.
+pub const DEFAULT_TRANSFER_TIMEOUT: time::Duration = time::Duration::from_secs(60);
 
 /// Control message used internally between workers, users, and the service.
 #[allow(clippy::large_enum_variant)]
@@ -97,23 +99,30 @@ impl Streams {
     }
 
     /// Open a new stream.
-    fn open(&mut self) -> (StreamId, worker::Channels) {
+    fn open(&mut self, capacity: Option<usize>) -> (StreamId, worker::Channels) {
         self.seq += 1;
 
         let id = StreamId::git(self.link)
             .nth(self.seq)
             .expect("Streams::open: too many streams");
         let channels = self
-            .register(id)
+            .register(id, capacity)
             .expect("Streams::open: stream was already open");
 
         (id, channels)
     }
 
-    /// Register an open stream.
-    fn register(&mut self, stream: StreamId) -> Option<worker::Channels> {
-        let (wire, worker) = worker::Channels::pair(DEFAULT_CHANNEL_TIMEOUT)
-            .expect("Streams::register: fatal: unable to create channels");
+    /// Register an open stream. The channels returned are bounded by `capacity`, if set.
+    fn register(&mut self, stream: StreamId, capacity: Option<usize>) -> Option<worker::Channels> {
+        let (wire, worker) = match capacity {
+            Some(capacity) => worker::Channels::bounded(
+                capacity,
+                DEFAULT_CHANNEL_TIMEOUT,
+                DEFAULT_TRANSFER_TIMEOUT,
+            ),
+            None => worker::Channels::pair(DEFAULT_CHANNEL_TIMEOUT, DEFAULT_TRANSFER_TIMEOUT),
+        }
+        .expect("Streams::register: fatal: unable to create channels");
 
         match self.streams.entry(stream) {
             Entry::Vacant(e) => {
@@ -326,6 +335,8 @@ pub struct Wire<R, S, W, G: Signer + Ecdh> {
     peers: Peers,
     /// SOCKS5 proxy address.
     proxy: net::SocketAddr,
+    /// Maximum number of events workers can queue on a stream, or `None` for no limit.
+    channel_capacity: Option<usize>,
 }
 
 impl<R, S, W, G> Wire<R, S, W, G>
@@ -340,6 +351,7 @@ where
         worker: chan::Sender<Task>,
         signer: G,
         proxy: net::SocketAddr,
+        channel_capacity: Option<usize>,
         clock: LocalTime,
     ) -> Self {
         service
@@ -351,6 +363,7 @@ where
             worker,
             signer,
             proxy,
+            channel_capacity,
             actions: VecDeque::new(),
             peers: Peers(HashMap::default()),
         }
@@ -558,7 +571,7 @@ where
                             })) => {
                                 log::debug!(target: "wire", "Received stream open for id={stream} from {nid}");
 
-                                let Some(channels) = streams.register(stream) else {
+                                let Some(channels) = streams.register(stream, self.channel_capacity) else {
                                     log::warn!(target: "wire", "Peer attempted to open already-open stream id={stream}");
                                     continue;
                                 };
@@ -824,6 +837,7 @@ where
                     rid,
                     remote,
                     namespaces,
+                    blocked,
                 } => {
                     log::trace!(target: "wire", "Processing fetch for {rid} from {remote}..");
 
@@ -836,7 +850,7 @@ where
                             log::error!(target: "wire", "Peer {remote} is not connected: dropping fetch");
                             continue;
                         };
-                    let (stream, channels) = streams.open();
+                    let (stream, channels) = streams.open(self.channel_capacity);
 
                     log::debug!(target: "wire", "Opened new stream with id={stream} for rid={rid} remote={remote}");
 
@@ -845,6 +859,7 @@ where
                         fetch: FetchRequest::Initiator {
                             rid,
                             namespaces,
+                            blocked,
                             remote,
                         },
                         stream,
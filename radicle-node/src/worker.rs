@@ -1,3 +1,4 @@
+mod cache;
 mod channels;
 mod fetch;
 mod tunnel;
@@ -5,41 +6,71 @@ mod tunnel;
 use std::collections::{BTreeSet, HashSet};
 use std::io::{prelude::*, BufReader};
 use std::ops::ControlFlow;
+use std::path::PathBuf;
 use std::{env, io, net, process, time};
 
 use crossbeam_channel as chan;
 
 use radicle::identity::Id;
+use radicle::node::Validation;
 use radicle::prelude::NodeId;
 use radicle::storage::{Namespaces, ReadRepository, RefUpdate};
 use radicle::{git, storage, Storage};
 
 use crate::runtime::{thread, Handle};
 use crate::wire::StreamId;
+use cache::RepositoryCache;
 use channels::{ChannelReader, ChannelWriter};
 use tunnel::Tunnel;
 
 pub use channels::{ChannelEvent, Channels};
 
+/// Default maximum number of git data chunks a worker can queue for the remote, before
+/// it has to wait for them to be sent.
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 256;
+/// Default maximum number of ref prefixes sent with a single `ls-remote`.
+pub const DEFAULT_LS_REFS_BATCH_SIZE: usize = 128;
+/// Number of refs matched by a single prefix, above which a warning is logged.
+pub const LS_REFS_WARN_LIMIT: usize = 4096;
+
 /// Worker pool configuration.
 pub struct Config {
     /// Number of worker threads.
     pub capacity: usize,
     /// Whether to use atomic fetches.
     pub atomic: bool,
-    /// Timeout for all operations.
-    pub timeout: time::Duration,
+    /// Time to wait for a remote to start responding, before giving up on it.
+    pub handshake_timeout: time::Duration,
+    /// Time to wait for more data, once a remote started responding. Should be long
+    /// enough for slow peers to send large packfiles.
+    pub transfer_timeout: time::Duration,
     /// Git daemon address.
     pub daemon: net::SocketAddr,
     /// Git storage.
     pub storage: Storage,
+    /// Path to the git binary.
+    pub git_binary: PathBuf,
+    /// Host that upload requests must specify, if any. Requests that specify another
+    /// host, or none, are rejected. Since peers fetch through a local tunnel, requests
+    /// for a loopback host are always accepted, so this only restricts clients that
+    /// address the node directly.
+    pub upload_host: Option<String>,
+    /// Maximum number of ref prefixes sent with a single `ls-remote`. Larger prefix
+    /// lists are split into multiple requests.
+    pub ls_refs_batch_size: usize,
 }
 
+/// The refs updated by a fetch, the remotes fetched from, and the remotes that were
+/// skipped because they failed validation.
+pub type Fetched = (Vec<RefUpdate>, HashSet<NodeId>, Vec<Validation>);
+
 /// Error returned by fetch.
 #[derive(thiserror::Error, Debug)]
 pub enum FetchError {
     #[error("the 'git fetch' command failed with exit code '{code}'")]
     CommandFailed { code: i32 },
+    #[error("the remote aborted the fetch: {message}")]
+    Remote { message: String },
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error(transparent)]
@@ -64,6 +95,13 @@ pub enum UploadError {
     DaemonConnectionFailed(io::Error),
     #[error("error parsing git command packet-line: {0}")]
     PacketLine(io::Error),
+    #[error("unsupported git service '{0}'")]
+    UnsupportedService(String),
+    #[error("git request for host {found:?} does not match expected host '{expected}'")]
+    HostMismatch {
+        expected: String,
+        found: Option<String>,
+    },
     #[error(transparent)]
     Io(#[from] io::Error),
 }
@@ -85,6 +123,8 @@ pub enum FetchRequest {
         rid: Id,
         /// Namespaces to fetch.
         namespaces: Namespaces,
+        /// Nodes whose refs are never transferred into storage.
+        blocked: HashSet<NodeId>,
         /// Remote peer we are interacting with.
         remote: NodeId,
     },
@@ -111,7 +151,7 @@ pub enum FetchResult {
         /// Repo fetched.
         rid: Id,
         /// Fetch result, including remotes fetched.
-        result: Result<(Vec<RefUpdate>, HashSet<NodeId>), FetchError>,
+        result: Result<Fetched, FetchError>,
     },
     Responder {
         /// Upload result.
@@ -141,9 +181,15 @@ struct Worker {
     storage: Storage,
     tasks: chan::Receiver<Task>,
     daemon: net::SocketAddr,
-    timeout: time::Duration,
+    handshake_timeout: time::Duration,
+    transfer_timeout: time::Duration,
     handle: Handle,
     atomic: bool,
+    git_binary: PathBuf,
+    upload_host: Option<String>,
+    ls_refs_batch_size: usize,
+    /// Production repositories opened by this worker.
+    repositories: RepositoryCache,
 }
 
 impl Worker {
@@ -186,14 +232,17 @@ impl Worker {
         stream: StreamId,
         mut channels: Channels,
     ) -> FetchResult {
+        channels.set_timeouts(self.handshake_timeout, self.transfer_timeout);
+
         match fetch {
             FetchRequest::Initiator {
                 rid,
                 namespaces,
+                blocked,
                 remote,
             } => {
                 log::debug!(target: "worker", "Worker processing outgoing fetch for {}", rid);
-                let result = self.fetch(rid, remote, stream, &namespaces, channels);
+                let result = self.fetch(rid, remote, stream, &namespaces, blocked, channels);
 
                 FetchResult::Initiator { rid, result }
             }
@@ -223,10 +272,16 @@ impl Worker {
         remote: NodeId,
         stream: StreamId,
         namespaces: &Namespaces,
+        blocked: HashSet<NodeId>,
         mut channels: Channels,
-    ) -> Result<(Vec<RefUpdate>, HashSet<NodeId>), FetchError> {
-        let staging =
-            fetch::StagingPhaseInitial::new(&self.storage, rid, self.nid, namespaces.clone())?;
+    ) -> Result<Fetched, FetchError> {
+        let staging = fetch::StagingPhaseInitial::new(
+            &self.storage,
+            rid,
+            self.nid,
+            namespaces.clone(),
+            blocked,
+        )?;
         let refs = if staging.repo.is_cloning() {
             match self._fetch(
                 &staging.repo,
@@ -281,7 +336,47 @@ impl Worker {
             }
         }
 
-        staging.transfer().map_err(FetchError::from)
+        let (updates, remotes, validations) = staging
+            .transfer(&mut self.repositories)
+            .map_err(FetchError::from)?;
+
+        for (remote, refs) in &updates.by_namespace {
+            let (created, updated, deleted) = fetch::Updates::categorize(refs);
+            log::debug!(
+                target: "worker",
+                "Fetched {} new, {} updated and {} deleted ref(s) of {remote} for {rid}",
+                created.len(),
+                updated.len(),
+                deleted.len()
+            );
+        }
+
+        // Objects are transferred to storage without git's usual housekeeping, so
+        // packs accumulate unless we trigger it ourselves.
+        if !updates.is_empty() {
+            self.gc(rid);
+        }
+        Ok((updates.refs, remotes, validations))
+    }
+
+    /// Let git pack loose objects and consolidate the packs of a stored repository, if
+    /// needed. This runs on its own thread so as not to hold up the worker, and failures
+    /// are only logged, since they don't affect the fetch result.
+    fn gc(&mut self, rid: Id) {
+        let path = match self.repositories.open(&self.storage, rid) {
+            Ok(repo) => repo.path().to_path_buf(),
+            Err(e) => {
+                log::warn!(target: "worker", "Failed to open {rid} for garbage collection: {e}");
+                return;
+            }
+        };
+        let git = self.git_binary.clone();
+
+        thread::spawn(&self.nid, "gc", move || {
+            if let Err(e) = git::gc_auto(&git, &path) {
+                log::warn!(target: "worker", "Garbage collection of {rid} failed: {e}");
+            }
+        });
     }
 
     fn upload_pack(
@@ -294,8 +389,8 @@ impl Worker {
         log::debug!(target: "worker", "Waiting for Git request pktline from {remote}..");
 
         // Read the request packet line to know what repository we're uploading.
-        let (rid, request) = match pktline::Reader::new(stream_r).read_request_pktline() {
-            Ok((req, pktline)) => (req.repo, pktline),
+        let (req, request) = match pktline::Reader::new(stream_r).read_request_pktline() {
+            Ok((req, pktline)) => (req, pktline),
             Err(err) if err.kind() == io::ErrorKind::ConnectionReset => {
                 log::debug!(
                     target: "worker",
@@ -303,12 +398,34 @@ impl Worker {
                 );
                 return Ok(ControlFlow::Break(()));
             }
+            Err(err) if err.kind() == io::ErrorKind::Unsupported => {
+                // Let the client know why its request is rejected, eg. when attempting to push.
+                let service = err.to_string();
+                stream_w.send(pktline::error(&format!("{service} is not supported")))?;
+                Self::eof(remote, stream, stream_w, &mut self.handle)?;
+
+                return Err(UploadError::UnsupportedService(service));
+            }
             Err(err) => {
                 return Err(UploadError::PacketLine(err));
             }
         };
+        let rid = req.repo;
         log::debug!(target: "worker", "Received Git request pktline for {rid}..");
 
+        if let Some(expected) = &self.upload_host {
+            if !req.is_for_host(expected) {
+                let err = UploadError::HostMismatch {
+                    expected: expected.clone(),
+                    found: req.host.map(|(host, _)| host),
+                };
+                stream_w.send(pktline::error(&err.to_string()))?;
+                Self::eof(remote, stream, stream_w, &mut self.handle)?;
+
+                return Err(err);
+            }
+        }
+
         match self._upload_pack(rid, remote, request, stream, stream_r, stream_w) {
             Ok(()) => {
                 log::debug!(target: "worker", "Upload of {rid} to {remote} on stream {stream} exited successfully");
@@ -331,12 +448,12 @@ impl Worker {
         log::debug!(target: "worker", "Connecting to daemon..");
 
         // Connect to our local git daemon, running as a child process.
-        let daemon = net::TcpStream::connect_timeout(&self.daemon, self.timeout)
+        let daemon = net::TcpStream::connect_timeout(&self.daemon, self.handshake_timeout)
             .map_err(UploadError::DaemonConnectionFailed)?;
         let (mut daemon_r, mut daemon_w) = (daemon.try_clone()?, daemon);
 
-        daemon_r.set_read_timeout(Some(self.timeout))?;
-        daemon_w.set_write_timeout(Some(self.timeout))?;
+        daemon_r.set_read_timeout(Some(self.transfer_timeout))?;
+        daemon_w.set_write_timeout(Some(self.transfer_timeout))?;
 
         // Write the raw request to the daemon, once we've parsed it.
         daemon_w.write_all(&request)?;
@@ -391,6 +508,8 @@ impl Worker {
         })
     }
 
+    /// List the remote refs matching the given prefixes. Prefixes are sent in batches of
+    /// at most `ls_refs_batch_size`, each with its own `ls-remote`.
     fn ls_refs(
         &self,
         repo: &fetch::StagedRepository,
@@ -398,10 +517,37 @@ impl Worker {
         remote: NodeId,
         stream: StreamId,
         channels: &mut Channels,
+    ) -> Result<BTreeSet<git::Namespaced<'static>>, FetchError> {
+        let prefixes = namespaces.into_iter().collect::<Vec<_>>();
+
+        ls_refs_batched(
+            &prefixes,
+            self.ls_refs_batch_size,
+            |batch| self._ls_refs(repo, batch, remote, stream, channels),
+            |prefix, matched| {
+                if matched > LS_REFS_WARN_LIMIT {
+                    log::warn!(
+                        target: "worker",
+                        "Prefix '{}' matched {matched} refs of {} on {remote}",
+                        prefix.as_str(),
+                        repo.id
+                    );
+                }
+            },
+        )
+    }
+
+    fn _ls_refs(
+        &self,
+        repo: &fetch::StagedRepository,
+        namespaces: &[git::PatternString],
+        remote: NodeId,
+        stream: StreamId,
+        channels: &mut Channels,
     ) -> Result<BTreeSet<git::Namespaced<'static>>, FetchError> {
         let tunnel = Tunnel::with(channels, stream, self.nid, remote, self.handle.clone())?;
         let tunnel_addr = tunnel.local_addr();
-        let mut cmd = process::Command::new("git");
+        let mut cmd = process::Command::new(&self.git_binary);
         cmd.current_dir(repo.path())
             .env_clear()
             .envs(env::vars().filter(|(k, _)| k == "PATH" || k.starts_with("GIT_TRACE")))
@@ -410,7 +556,7 @@ impl Worker {
             .arg("ls-remote")
             .arg(format!("git://{tunnel_addr}/{}", repo.id.canonical()));
 
-        for ns in namespaces.into_iter() {
+        for ns in namespaces {
             cmd.arg(ns.as_str());
         }
 
@@ -459,7 +605,7 @@ impl Worker {
                 }
             });
 
-            tunnel.run(self.timeout)?;
+            tunnel.run(self.transfer_timeout)?;
 
             Ok::<_, FetchError>(())
         })?;
@@ -489,7 +635,7 @@ impl Worker {
     {
         let tunnel = Tunnel::with(channels, stream, self.nid, remote, self.handle.clone())?;
         let tunnel_addr = tunnel.local_addr();
-        let mut cmd = process::Command::new("git");
+        let mut cmd = process::Command::new(&self.git_binary);
         cmd.current_dir(repo.path())
             .env_clear()
             .envs(env::vars().filter(|(k, _)| k == "PATH" || k.starts_with("GIT_TRACE")))
@@ -527,17 +673,28 @@ impl Worker {
         let mut child = cmd.spawn()?;
         let stderr = child.stderr.take().unwrap();
 
-        thread::spawn(&self.nid, "fetch", || {
+        // Nb. Git prints the remote's progress messages prefixed with `remote: `, and
+        // its `ERR` packets as `fatal: remote error: <message>`. We keep the latter,
+        // so that the failure can be reported with the server's reason.
+        let reader = thread::spawn(&self.nid, "fetch", || {
+            let mut error = None;
             for line in BufReader::new(stderr).lines().flatten() {
                 log::debug!(target: "worker", "Git: {}", line);
+
+                if let Some(message) = remote_error(&line) {
+                    error = Some(message.to_owned());
+                }
             }
+            error
         });
 
-        tunnel.run(self.timeout)?;
+        tunnel.run(self.transfer_timeout)?;
 
         let result = child.wait()?;
         if result.success() {
             Ok(())
+        } else if let Ok(Some(message)) = reader.join() {
+            Err(FetchError::Remote { message })
         } else {
             Err(FetchError::CommandFailed {
                 code: result.code().unwrap_or(1),
@@ -564,6 +721,43 @@ impl Worker {
     }
 }
 
+/// List refs with `ls_refs`, passing it at most `batch_size` prefixes at a time, and
+/// combine the results. `matched` is called with each prefix and the number of refs it
+/// matched within its batch.
+fn ls_refs_batched<E>(
+    prefixes: &[git::PatternString],
+    batch_size: usize,
+    mut ls_refs: impl FnMut(&[git::PatternString]) -> Result<BTreeSet<git::Namespaced<'static>>, E>,
+    mut matched: impl FnMut(&git::PatternString, usize),
+) -> Result<BTreeSet<git::Namespaced<'static>>, E> {
+    let mut refs = BTreeSet::new();
+
+    for batch in prefixes.chunks(batch_size.max(1)) {
+        let listed = ls_refs(batch)?;
+        let names = listed
+            .iter()
+            .map(|r| r.clone().to_ref_string())
+            .collect::<Vec<_>>();
+
+        for prefix in batch {
+            let stem = prefix.as_str().trim_end_matches('*');
+            let count = names
+                .iter()
+                .filter(|n| n.as_str().starts_with(stem))
+                .count();
+
+            matched(prefix, count);
+        }
+        refs.extend(listed);
+    }
+    Ok(refs)
+}
+
+/// Get the message of a remote `ERR` packet, from a line printed by git on `stderr`.
+fn remote_error(line: &str) -> Option<&str> {
+    line.strip_prefix("fatal: remote error: ").map(str::trim)
+}
+
 /// A pool of workers. One thread is allocated for each worker.
 pub struct Pool {
     pool: Vec<thread::JoinHandle<Result<(), chan::RecvError>>>,
@@ -580,8 +774,13 @@ impl Pool {
                 handle: handle.clone(),
                 storage: config.storage.clone(),
                 daemon: config.daemon,
-                timeout: config.timeout,
+                handshake_timeout: config.handshake_timeout,
+                transfer_timeout: config.transfer_timeout,
                 atomic: config.atomic,
+                git_binary: config.git_binary.clone(),
+                upload_host: config.upload_host.clone(),
+                ls_refs_batch_size: config.ls_refs_batch_size,
+                repositories: RepositoryCache::default(),
             };
             let thread = thread::spawn(&nid, format!("worker#{i}"), || worker.run());
 
@@ -628,30 +827,83 @@ pub mod pktline {
         ///
         /// Example: `0032git-upload-pack /project.git\0host=myserver.com\0`
         ///
+        /// Legacy clients may send the request unframed, terminated by a newline, eg.
+        /// `git-upload-pack /project.git\0host=myserver.com\0\n`. In that case, the
+        /// returned packet-line is framed, so that it can be passed on to the daemon as-is.
         pub fn read_request_pktline(&mut self) -> io::Result<(GitRequest, Vec<u8>)> {
             let mut pktline = [0u8; 1024];
-            let length = self.read_pktline(&mut pktline)?;
+            self.read_exact(&mut pktline[..HEADER_LEN])?;
+
+            if &pktline[..HEADER_LEN] == b"git-" {
+                return self.read_legacy_request(&mut pktline);
+            }
+            let length = self.read_pktline_body(&mut pktline)?;
             let Some(cmd) = GitRequest::parse(&pktline[4..length]) else {
-                return Err(io::ErrorKind::InvalidInput.into());
+                return Err(invalid_request(&pktline[4..length]));
             };
             Ok((cmd, Vec::from(&pktline[..length])))
         }
 
-        /// Parse a Git packet-line.
-        fn read_pktline(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-            self.read_exact(&mut buf[..HEADER_LEN])?;
+        /// Read the rest of an unframed request, whose first bytes are already in `buf`.
+        fn read_legacy_request(&mut self, buf: &mut [u8]) -> io::Result<(GitRequest, Vec<u8>)> {
+            let mut length = HEADER_LEN;
+            loop {
+                if length == buf.len() {
+                    return Err(io::ErrorKind::InvalidInput.into());
+                }
+                self.read_exact(&mut buf[length..length + 1])?;
+                if buf[length] == b'\n' {
+                    break;
+                }
+                length += 1;
+            }
+            let line = &buf[..length];
+            let Some(cmd) = GitRequest::parse(line) else {
+                return Err(invalid_request(line));
+            };
+            let mut pktline = format!("{:04x}", line.len() + HEADER_LEN).into_bytes();
+            pktline.extend_from_slice(line);
+
+            Ok((cmd, pktline))
+        }
 
+        /// Parse a Git packet-line, whose length header is already in `buf`.
+        fn read_pktline_body(&mut self, buf: &mut [u8]) -> io::Result<usize> {
             let length = str::from_utf8(&buf[..HEADER_LEN])
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
             let length = usize::from_str_radix(length, 16)
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
 
+            if length < HEADER_LEN || length > buf.len() {
+                return Err(io::ErrorKind::InvalidInput.into());
+            }
             self.read_exact(&mut buf[HEADER_LEN..length])?;
 
             Ok(length)
         }
     }
 
+    /// Error for a request that couldn't be parsed. Requests for services other than
+    /// `git-upload-pack` result in an [`io::ErrorKind::Unsupported`] error carrying the
+    /// service name.
+    fn invalid_request(line: &[u8]) -> io::Error {
+        const UNSUPPORTED: [&str; 2] = ["git-receive-pack", "git-upload-archive"];
+
+        let service = line.split(|b| *b == b' ').next().unwrap_or_default();
+        match UNSUPPORTED.iter().find(|s| s.as_bytes() == service) {
+            Some(service) => io::Error::new(io::ErrorKind::Unsupported, *service),
+            None => io::ErrorKind::InvalidInput.into(),
+        }
+    }
+
+    /// Create an error packet-line, which git clients display to the user.
+    pub fn error(msg: &str) -> Vec<u8> {
+        let msg = format!("ERR {msg}\n");
+        let mut pktline = format!("{:04x}", msg.len() + HEADER_LEN).into_bytes();
+        pktline.extend_from_slice(msg.as_bytes());
+        pktline
+    }
+
     impl<'a, R: io::Read> io::Read for Reader<'a, R> {
         fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
             self.stream.read(buf)
@@ -667,6 +919,19 @@ pub mod pktline {
     }
 
     impl GitRequest {
+        /// Check whether the request was made for the given host. Requests for a loopback
+        /// host are made by peers through their local tunnel, and are accepted regardless.
+        pub fn is_for_host(&self, expected: &str) -> bool {
+            let Some((host, _)) = &self.host else {
+                return false;
+            };
+            host == expected
+                || host == "localhost"
+                || host
+                    .parse::<std::net::IpAddr>()
+                    .map_or(false, |ip| ip.is_loopback())
+        }
+
         /// Parse a Git command from a packet-line.
         fn parse(input: &[u8]) -> Option<Self> {
             let input = str::from_utf8(input).ok()?;
@@ -705,4 +970,161 @@ pub mod pktline {
             })
         }
     }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        const REQUEST: &str = "git-upload-pack /z2w8RArM3gaBXZxXhQUswE3hhLcss\0host=seed.xyz\0";
+
+        #[test]
+        fn test_read_request_pktline() {
+            let input = format!("{:04x}{REQUEST}", REQUEST.len() + HEADER_LEN).into_bytes();
+            let mut stream = io::Cursor::new(input.clone());
+            let (req, pktline) = Reader::new(&mut stream).read_request_pktline().unwrap();
+
+            assert_eq!(req.host, Some((String::from("seed.xyz"), None)));
+            assert_eq!(pktline, input);
+        }
+
+        #[test]
+        fn test_read_legacy_request() {
+            let mut stream = io::Cursor::new(format!("{REQUEST}\n").into_bytes());
+            let (req, pktline) = Reader::new(&mut stream).read_request_pktline().unwrap();
+
+            assert_eq!(req.path, "/z2w8RArM3gaBXZxXhQUswE3hhLcss");
+            assert_eq!(
+                pktline,
+                format!("{:04x}{REQUEST}", REQUEST.len() + HEADER_LEN).into_bytes()
+            );
+        }
+
+        #[test]
+        fn test_request_is_for_host() {
+            let req = GitRequest::parse(REQUEST.as_bytes()).unwrap();
+
+            assert!(req.is_for_host("seed.xyz"));
+            assert!(!req.is_for_host("seed.radicle.xyz"));
+
+            let tunnelled =
+                "git-upload-pack /z2w8RArM3gaBXZxXhQUswE3hhLcss\0host=127.0.0.1:51423\0";
+            let req = GitRequest::parse(tunnelled.as_bytes()).unwrap();
+
+            assert!(req.is_for_host("seed.xyz"));
+
+            let anonymous = "git-upload-pack /z2w8RArM3gaBXZxXhQUswE3hhLcss\0";
+            let req = GitRequest::parse(anonymous.as_bytes()).unwrap();
+
+            assert!(!req.is_for_host("seed.xyz"));
+        }
+
+        #[test]
+        fn test_read_unsupported_request() {
+            let request = "git-receive-pack /z2w8RArM3gaBXZxXhQUswE3hhLcss\0";
+            let input = format!("{:04x}{request}", request.len() + HEADER_LEN).into_bytes();
+            let mut stream = io::Cursor::new(input);
+            let err = Reader::new(&mut stream).read_request_pktline().unwrap_err();
+
+            assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+            assert_eq!(err.to_string(), "git-receive-pack");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_remote_error() {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            pktline::Reader::new(&mut stream)
+                .read_request_pktline()
+                .unwrap();
+            stream
+                .write_all(&pktline::error("repository is not available"))
+                .unwrap();
+        });
+        let output = process::Command::new("git")
+            .env_clear()
+            .envs(env::vars().filter(|(k, _)| k == "PATH"))
+            .envs(git::env::GIT_DEFAULT_CONFIG)
+            .arg("ls-remote")
+            .arg(format!("git://{addr}/z2w8RArM3gaBXZxXhQUswE3hhLcss"))
+            .output()
+            .unwrap();
+        server.join().unwrap();
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+
+        assert!(!output.status.success());
+        assert_eq!(
+            stderr.lines().find_map(remote_error),
+            Some("repository is not available")
+        );
+        assert_eq!(remote_error("remote: Enumerating objects: 3, done."), None);
+    }
+
+    #[test]
+    fn test_ls_refs_batched() {
+        let remotes = [
+            crate::test::arbitrary::gen::<NodeId>(1),
+            crate::test::arbitrary::gen::<NodeId>(1),
+            crate::test::arbitrary::gen::<NodeId>(1),
+        ];
+        let refs = remotes
+            .iter()
+            .flat_map(|pk| {
+                [git::refname!("master"), git::refname!("dev")]
+                    .map(|b| git::refs::storage::branch_of(pk, &b).to_owned())
+            })
+            .collect::<BTreeSet<_>>();
+        let prefixes = remotes
+            .iter()
+            .map(|pk| pk.to_namespace().with_pattern(git::refspec::STAR))
+            .collect::<Vec<_>>();
+
+        // Stands in for `ls-remote`, listing the refs under any of the given prefixes.
+        let ls_refs = |batch: &[git::PatternString]| {
+            Ok::<_, ()>(
+                refs.iter()
+                    .filter(|r| {
+                        let name = r.clone().to_ref_string();
+                        batch
+                            .iter()
+                            .any(|p| name.as_str().starts_with(p.as_str().trim_end_matches('*')))
+                    })
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        for batch_size in [1, 2, 3] {
+            let mut batches = 0;
+            let mut matched = Vec::new();
+            let result = ls_refs_batched(
+                &prefixes,
+                batch_size,
+                |batch| {
+                    batches += 1;
+                    ls_refs(batch)
+                },
+                |prefix, count| matched.push((prefix.clone(), count)),
+            )
+            .unwrap();
+
+            assert_eq!(result, refs);
+            assert_eq!(batches, (prefixes.len() + batch_size - 1) / batch_size);
+            assert_eq!(
+                matched,
+                prefixes.iter().map(|p| (p.clone(), 2)).collect::<Vec<_>>()
+            );
+        }
+    }
 }
@@ -0,0 +1,128 @@
+use std::collections::{HashMap, VecDeque};
+
+use radicle::identity::Id;
+use radicle::storage::git::Repository;
+use radicle::storage::{Error, ReadStorage, WriteStorage};
+use radicle::Storage;
+
+/// Default number of repositories kept open by a worker.
+pub const DEFAULT_CAPACITY: usize = 32;
+
+/// A bounded cache of open storage repositories, keyed by [`Id`].
+///
+/// Each worker owns its own cache, so handles are never shared between threads and no
+/// locking is required. When the cache is full, the least recently used repository is
+/// closed to make room.
+pub struct RepositoryCache {
+    capacity: usize,
+    repos: HashMap<Id, Repository>,
+    /// Repositories, from least to most recently used.
+    order: VecDeque<Id>,
+}
+
+impl Default for RepositoryCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl RepositoryCache {
+    /// Create a new cache holding at most `capacity` repositories.
+    ///
+    /// The capacity is at least one, since the most recently opened repository is always
+    /// kept around.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            capacity,
+            repos: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Get the repository with the given id, opening it if it isn't cached.
+    pub fn open(&mut self, storage: &Storage, rid: Id) -> Result<&Repository, Error> {
+        if self.repos.contains_key(&rid) {
+            self.touch(rid);
+        } else {
+            let repo = storage.repository(rid)?;
+            self.insert(repo);
+        }
+        Ok(&self.repos[&rid])
+    }
+
+    /// Create a new repository with the given id and cache it.
+    pub fn create(&mut self, storage: &Storage, rid: Id) -> Result<&Repository, Error> {
+        let repo = storage.create(rid)?;
+        self.insert(repo);
+
+        Ok(&self.repos[&rid])
+    }
+
+    /// Close the repository with the given id, if it's open.
+    pub fn remove(&mut self, rid: &Id) -> Option<Repository> {
+        self.order.retain(|id| id != rid);
+        self.repos.remove(rid)
+    }
+
+    fn insert(&mut self, repo: Repository) {
+        let rid = repo.id;
+
+        if self.remove(&rid).is_none() && self.repos.len() >= self.capacity {
+            if let Some(lru) = self.order.pop_front() {
+                log::debug!(target: "worker", "Closing cached repository {lru}");
+                self.repos.remove(&lru);
+            }
+        }
+        self.order.push_back(rid);
+        self.repos.insert(rid, repo);
+    }
+
+    fn touch(&mut self, rid: Id) {
+        self.order.retain(|id| *id != rid);
+        self.order.push_back(rid);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crypto::test::signer::MockSigner;
+    use crate::test::fixtures;
+
+    use super::*;
+
+    #[test]
+    fn test_repository_reused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let signer = MockSigner::default();
+        let storage = fixtures::storage(tmp.path(), &signer).unwrap();
+        let rids = storage.inventory().unwrap();
+        let mut cache = RepositoryCache::new(2);
+
+        let first = cache.open(&storage, rids[0]).unwrap() as *const Repository;
+        let second = cache.open(&storage, rids[0]).unwrap() as *const Repository;
+
+        assert_eq!(cache.repos.len(), 1);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_least_recently_used_evicted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let signer = MockSigner::default();
+        let storage = fixtures::storage(tmp.path(), &signer).unwrap();
+        let rids = storage.inventory().unwrap();
+        let mut cache = RepositoryCache::new(2);
+
+        cache.open(&storage, rids[0]).unwrap();
+        cache.open(&storage, rids[1]).unwrap();
+        cache.open(&storage, rids[0]).unwrap();
+        cache.open(&storage, rids[2]).unwrap();
+
+        assert_eq!(cache.repos.len(), 2);
+        assert!(cache.repos.contains_key(&rids[0]));
+        assert!(cache.repos.contains_key(&rids[2]));
+        assert!(!cache.repos.contains_key(&rids[1]));
+    }
+}
@@ -38,27 +38,68 @@ pub struct Channels<T = Vec<u8>> {
 }
 
 impl<T: AsRef<[u8]>> Channels<T> {
+    /// Create new channels. Reads time out after `handshake_timeout` until the first data
+    /// is received, and after `transfer_timeout` from then on. Writes time out after
+    /// `transfer_timeout`.
     pub fn new(
         sender: chan::Sender<ChannelEvent<T>>,
         receiver: chan::Receiver<ChannelEvent<T>>,
-        timeout: time::Duration,
+        handshake_timeout: time::Duration,
+        transfer_timeout: time::Duration,
     ) -> Self {
-        let sender = ChannelWriter { sender, timeout };
-        let receiver = ChannelReader::new(receiver, timeout);
+        let sender = ChannelWriter {
+            sender,
+            timeout: transfer_timeout,
+        };
+        let receiver = ChannelReader::new(receiver, handshake_timeout, transfer_timeout);
 
         Self { sender, receiver }
     }
 
-    pub fn pair(timeout: time::Duration) -> io::Result<(Channels<T>, Channels<T>)> {
+    pub fn pair(
+        handshake_timeout: time::Duration,
+        transfer_timeout: time::Duration,
+    ) -> io::Result<(Channels<T>, Channels<T>)> {
         let (l_send, r_recv) = chan::unbounded::<ChannelEvent<T>>();
         let (r_send, l_recv) = chan::unbounded::<ChannelEvent<T>>();
 
-        let l = Channels::new(l_send, l_recv, timeout);
-        let r = Channels::new(r_send, r_recv, timeout);
+        let l = Channels::new(l_send, l_recv, handshake_timeout, transfer_timeout);
+        let r = Channels::new(r_send, r_recv, handshake_timeout, transfer_timeout);
 
         Ok((l, r))
     }
 
+    /// Like [`Channels::pair`], except that at most `capacity` events sent from the first
+    /// channels can be queued. Once full, sending blocks until the other side catches up,
+    /// or the transfer timeout elapses.
+    ///
+    /// Nb. Only the first channels are bounded: the other side is meant to be driven by the
+    /// reactor, which shouldn't ever block on a send.
+    pub fn bounded(
+        capacity: usize,
+        handshake_timeout: time::Duration,
+        transfer_timeout: time::Duration,
+    ) -> io::Result<(Channels<T>, Channels<T>)> {
+        let (l_send, r_recv) = chan::bounded::<ChannelEvent<T>>(capacity);
+        let (r_send, l_recv) = chan::unbounded::<ChannelEvent<T>>();
+
+        let l = Channels::new(l_send, l_recv, handshake_timeout, transfer_timeout);
+        let r = Channels::new(r_send, r_recv, handshake_timeout, transfer_timeout);
+
+        Ok((l, r))
+    }
+
+    /// Replace the timeouts the channels were created with.
+    pub fn set_timeouts(
+        &mut self,
+        handshake_timeout: time::Duration,
+        transfer_timeout: time::Duration,
+    ) {
+        self.sender.timeout = transfer_timeout;
+        self.receiver.handshake_timeout = handshake_timeout;
+        self.receiver.transfer_timeout = transfer_timeout;
+    }
+
     pub fn try_iter(&self) -> impl Iterator<Item = ChannelEvent<T>> + '_ {
         self.receiver.try_iter()
     }
@@ -81,7 +122,12 @@ impl<T: AsRef<[u8]>> Channels<T> {
 pub struct ChannelReader<T = Vec<u8>> {
     buffer: io::Cursor<Vec<u8>>,
     receiver: chan::Receiver<ChannelEvent<T>>,
-    timeout: time::Duration,
+    /// Time to wait for the first data, so that unresponsive peers are dropped quickly.
+    handshake_timeout: time::Duration,
+    /// Time to wait for data once the remote has started sending.
+    transfer_timeout: time::Duration,
+    /// Whether any data was received yet.
+    receiving: bool,
 }
 
 impl<T> Deref for ChannelReader<T> {
@@ -93,18 +139,40 @@ impl<T> Deref for ChannelReader<T> {
 }
 
 impl<T: AsRef<[u8]>> ChannelReader<T> {
-    pub fn new(receiver: chan::Receiver<ChannelEvent<T>>, timeout: time::Duration) -> Self {
+    pub fn new(
+        receiver: chan::Receiver<ChannelEvent<T>>,
+        handshake_timeout: time::Duration,
+        transfer_timeout: time::Duration,
+    ) -> Self {
         Self {
             buffer: io::Cursor::new(Vec::new()),
             receiver,
-            timeout,
+            handshake_timeout,
+            transfer_timeout,
+            receiving: false,
+        }
+    }
+
+    /// The timeout that applies to the next read.
+    ///
+    /// Nb. The switch to the transfer timeout happens on the first data of any kind, which
+    /// is usually the ref advertisement, not the first byte of the packfile. Computing the
+    /// pack can thus take up to the transfer timeout.
+    fn timeout(&self) -> time::Duration {
+        if self.receiving {
+            self.transfer_timeout
+        } else {
+            self.handshake_timeout
         }
     }
 
     pub fn pipe<W: io::Write>(&mut self, mut writer: W) -> io::Result<()> {
         loop {
-            match self.receiver.recv_timeout(self.timeout) {
-                Ok(ChannelEvent::Data(data)) => writer.write_all(data.as_ref())?,
+            match self.receiver.recv_timeout(self.timeout()) {
+                Ok(ChannelEvent::Data(data)) => {
+                    self.receiving = true;
+                    writer.write_all(data.as_ref())?
+                }
                 Ok(ChannelEvent::Eof) => return Ok(()),
                 Ok(ChannelEvent::Close) => return Err(io::ErrorKind::ConnectionReset.into()),
                 Err(chan::RecvTimeoutError::Timeout) => {
@@ -131,8 +199,9 @@ impl Read for ChannelReader<Vec<u8>> {
             return Ok(read);
         }
 
-        match self.receiver.recv_timeout(self.timeout) {
+        match self.receiver.recv_timeout(self.timeout()) {
             Ok(ChannelEvent::Data(data)) => {
+                self.receiving = true;
                 self.buffer = io::Cursor::new(data);
                 self.buffer.read(buf)
             }
@@ -189,3 +258,53 @@ impl<T: AsRef<[u8]>> ChannelWriter<T> {
         self.sender.send(ChannelEvent::Close)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_timeouts() {
+        let handshake = time::Duration::from_millis(10);
+        let transfer = time::Duration::from_secs(1);
+        let (mut worker, wire) = Channels::<Vec<u8>>::pair(handshake, transfer).unwrap();
+        let (_, reader) = worker.split();
+        let mut buf = [0u8; 8];
+
+        // Nothing was received yet, so the handshake timeout applies.
+        let err = reader.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+        wire.send(ChannelEvent::Data(vec![1])).unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+
+        // From now on, the transfer timeout applies.
+        let sender = std::thread::spawn(move || {
+            std::thread::sleep(time::Duration::from_millis(100));
+            wire.send(ChannelEvent::Data(vec![2])).unwrap();
+        });
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 2);
+
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn test_bounded_send_times_out() {
+        let timeout = time::Duration::from_millis(10);
+        let (worker, wire) = Channels::<Vec<u8>>::bounded(1, timeout, timeout).unwrap();
+
+        worker.send(ChannelEvent::Data(vec![1])).unwrap();
+        let err = worker.send(ChannelEvent::Data(vec![2])).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+        // Once the other side catches up, sending works again.
+        assert_eq!(wire.try_iter().count(), 1);
+        worker.send(ChannelEvent::Data(vec![3])).unwrap();
+
+        // The other side is never bounded.
+        for _ in 0..8 {
+            wire.send(ChannelEvent::Data(vec![4])).unwrap();
+        }
+    }
+}
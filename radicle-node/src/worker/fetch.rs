@@ -3,12 +3,13 @@ pub use refspecs::SpecialRefs;
 
 pub mod error;
 
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
 
 use radicle::crypto::{PublicKey, Unverified, Verified};
 use radicle::git::refspec;
 use radicle::git::{url, Namespaced};
+use radicle::node::{Severity, Validation};
 use radicle::prelude::{Doc, Id, NodeId};
 use radicle::storage::git::Repository;
 use radicle::storage::refs::IDENTITY_BRANCH;
@@ -16,6 +17,8 @@ use radicle::storage::{Namespaces, RefUpdate, Remote, RemoteId};
 use radicle::storage::{ReadRepository, ReadStorage, WriteRepository, WriteStorage};
 use radicle::{git, Storage};
 
+use super::cache::RepositoryCache;
+
 pub type Refspec = refspec::Refspec<git::PatternString, git::PatternString>;
 
 /// The initial phase of staging a fetch from a remote.
@@ -35,6 +38,8 @@ pub struct StagingPhaseInitial<'a> {
     nid: NodeId,
     /// The `Namespaces` passed by the fetching caller.
     pub(super) namespaces: Namespaces,
+    /// Remotes that are blocked, and never transferred into production.
+    blocked: HashSet<NodeId>,
     _tmp: tempfile::TempDir,
 }
 
@@ -90,6 +95,48 @@ impl Deref for FinalStagedRepository {
     }
 }
 
+/// The references updated by transferring a fetch into production.
+#[derive(Debug, Default)]
+pub struct Updates {
+    /// All ref updates, in the order they were made.
+    pub refs: Vec<RefUpdate>,
+    /// The same ref updates, grouped by the remote they belong to.
+    pub by_namespace: HashMap<PublicKey, Vec<RefUpdate>>,
+}
+
+impl Updates {
+    /// Whether no ref was updated.
+    pub fn is_empty(&self) -> bool {
+        self.refs.is_empty()
+    }
+
+    /// Split the given updates into created, updated and deleted refs, in that order.
+    /// Skipped refs are left out.
+    pub fn categorize<'a>(
+        updates: impl IntoIterator<Item = &'a RefUpdate>,
+    ) -> (Vec<&'a RefUpdate>, Vec<&'a RefUpdate>, Vec<&'a RefUpdate>) {
+        let (mut created, mut updated, mut deleted) = (Vec::new(), Vec::new(), Vec::new());
+
+        for update in updates {
+            match update {
+                RefUpdate::Created { .. } => created.push(update),
+                RefUpdate::Updated { .. } => updated.push(update),
+                RefUpdate::Deleted { .. } => deleted.push(update),
+                RefUpdate::Skipped { .. } => {}
+            }
+        }
+        (created, updated, deleted)
+    }
+
+    fn push(&mut self, remote: PublicKey, update: RefUpdate) {
+        self.by_namespace
+            .entry(remote)
+            .or_default()
+            .push(update.clone());
+        self.refs.push(update);
+    }
+}
+
 /// The second, and final, phase of staging a fetch from a remote.
 ///
 /// The [`StagingPhaseFinal::refpsecs`] generated are to fetch any follow-up
@@ -108,6 +155,8 @@ pub struct StagingPhaseFinal<'a> {
     production: &'a Storage,
     /// The local Node ID.
     nid: NodeId,
+    /// Remotes that are blocked, and never transferred into production.
+    blocked: HashSet<NodeId>,
     _tmp: tempfile::TempDir,
 }
 
@@ -115,6 +164,12 @@ enum VerifiedRemote {
     Failed {
         reason: String,
     },
+    /// The remote's signed refs are older than the ones we have, ie. the remote is
+    /// replaying a previous set of refs.
+    Stale {
+        local: git::Oid,
+        staging: git::Oid,
+    },
     Success {
         // Nb. unused but we want to ensure that we verify the identity
         _doc: Doc<Verified>,
@@ -133,6 +188,7 @@ impl<'a> StagingPhaseInitial<'a> {
         rid: Id,
         nid: NodeId,
         namespaces: Namespaces,
+        blocked: HashSet<NodeId>,
     ) -> Result<Self, error::Init> {
         let tmp = tempfile::TempDir::new()?;
         log::debug!(target: "worker", "Staging fetch in {:?}", tmp.path());
@@ -143,6 +199,7 @@ impl<'a> StagingPhaseInitial<'a> {
             nid,
             production,
             namespaces,
+            blocked,
             _tmp: tmp,
         })
     }
@@ -205,6 +262,7 @@ impl<'a> StagingPhaseInitial<'a> {
             repo,
             nid: self.nid,
             production: self.production,
+            blocked: self.blocked,
             _tmp: self._tmp,
         })
     }
@@ -278,7 +336,8 @@ impl<'a> StagingPhaseFinal<'a> {
     ///
     /// Verify all `rad/id` and `rad/sigrefs` from fetched
     /// remotes. Any remotes that fail will be ignored and not fetched
-    /// into the production repository.
+    /// into the production repository. Blocked remotes are ignored without
+    /// being verified.
     ///
     /// For each remote that verifies, fetch from the staging storage
     /// into the production storage using the refspec:
@@ -287,30 +346,36 @@ impl<'a> StagingPhaseFinal<'a> {
     /// refs/namespaces/<remote>/*:refs/namespaces/<remote>/*
     /// ```
     ///
-    /// All references that were updated are returned as a
-    /// [`RefUpdate`].
-    pub fn transfer(self) -> Result<(Vec<RefUpdate>, HashSet<NodeId>), error::Transfer> {
+    /// All references that were updated are returned as [`Updates`], along with the
+    /// remotes that were fetched and the validation failures of those that were skipped.
+    ///
+    /// The production repository is looked up in, or added to, the given cache.
+    pub fn transfer(
+        self,
+        cache: &mut RepositoryCache,
+    ) -> Result<(Updates, HashSet<NodeId>, Vec<Validation>), error::Transfer> {
         // Nb. we have to verify in a different order when fetching vs. cloning, due to needing
         // access to the existing repository in the fetching case.
         let (production, verifications) = match &self.repo {
             FinalStagedRepository::Cloning { repo, .. } => {
                 let verifications = self.verify::<Repository>(None)?;
-                let prod = self.production.create(repo.id)?;
+                let prod = cache.create(self.production, repo.id)?;
 
                 (prod, verifications)
             }
             FinalStagedRepository::Fetching { repo, .. } => {
-                let prod = self.production.repository(repo.id)?;
-                let verifications = self.verify(Some(&prod))?;
+                let prod = cache.open(self.production, repo.id)?;
+                let verifications = self.verify(Some(prod))?;
 
                 (prod, verifications)
             }
         };
         let url = url::File::new(self.repo.path().to_path_buf()).to_string();
         let mut remote = production.backend.remote_anonymous(&url)?;
-        let mut updates = Vec::new();
+        let mut updates = Updates::default();
         let mut delete = HashSet::new();
         let mut skipped = HashSet::new();
+        let mut validations = Vec::new();
 
         let callbacks = ref_updates(&mut updates);
         let mut remotes = {
@@ -324,12 +389,27 @@ impl<'a> StagingPhaseFinal<'a> {
                         vec![]
                     }
                     VerifiedRemote::Failed { reason } => {
-                        // TODO: We should include the skipped remotes in the fetch result,
-                        // with the reason why they're skipped.
-                        log::warn!(
+                        log::error!(
                             target: "worker",
                             "{remote} failed to verify, ignoring ref updates: {reason}",
                         );
+                        validations.push(Validation {
+                            remote,
+                            severity: Severity::Error,
+                            reason,
+                        });
+                        vec![]
+                    }
+                    VerifiedRemote::Stale { local, staging } => {
+                        log::warn!(
+                            target: "worker",
+                            "{remote} has stale signed refs ({staging} is behind {local}), ignoring ref updates",
+                        );
+                        validations.push(Validation {
+                            remote,
+                            severity: Severity::Warning,
+                            reason: format!("signed refs are behind ours: {staging} < {local}"),
+                        });
                         vec![]
                     }
                     VerifiedRemote::Success {
@@ -448,7 +528,7 @@ impl<'a> StagingPhaseFinal<'a> {
         // This confirms to the user that the remote was indeed tried.
         remotes.extend(skipped);
 
-        Ok((updates, remotes))
+        Ok((updates, remotes, validations))
     }
 
     fn remotes(&self) -> Result<Box<dyn Iterator<Item = Remote> + '_>, git::raw::Error> {
@@ -478,6 +558,13 @@ impl<'a> StagingPhaseFinal<'a> {
         let result = self
             .remotes()?
             .filter(|remote| remote.id != self.nid || self.repo.is_cloning())
+            .filter(|remote| {
+                if self.blocked.contains(&remote.id) {
+                    log::debug!(target: "worker", "Ignoring blocked remote {}", remote.id);
+                    return false;
+                }
+                true
+            })
             .map(|remote| {
                 let remote_id = remote.id;
 
@@ -500,12 +587,21 @@ impl<'a> StagingPhaseFinal<'a> {
                                     log::debug!(target: "worker", "Signed refs for {remote_id} fast-foward: {local} -> {staging}");
                                 }
                                 Ok(false) => {
-                                    return (
-                                        remote_id,
+                                    // Nb. If ours descend from the fetched ones, the remote is
+                                    // rolling back its refs rather than diverging.
+                                    let stale = self
+                                        .repo
+                                        .backend
+                                        .graph_descendant_of(local.into(), staging.into())
+                                        .unwrap_or(false);
+                                    let verification = if stale {
+                                        VerifiedRemote::Stale { local, staging }
+                                    } else {
                                         VerifiedRemote::Failed {
                                             reason: "signed refs have diverged".to_owned()
                                         }
-                                    );
+                                    };
+                                    return (remote_id, verification);
                                 }
                                 Err(e) => {
                                     return (
@@ -543,12 +639,16 @@ impl<'a> StagingPhaseFinal<'a> {
     }
 }
 
-fn ref_updates(updates: &mut Vec<RefUpdate>) -> git::raw::RemoteCallbacks<'_> {
+fn ref_updates(updates: &mut Updates) -> git::raw::RemoteCallbacks<'_> {
     let mut callbacks = git::raw::RemoteCallbacks::new();
     callbacks.update_tips(|name, old, new| {
         if let Ok(name) = git::RefString::try_from(name) {
-            if name.to_namespaced().is_some() {
-                updates.push(RefUpdate::from(name, old, new));
+            let remote = name
+                .to_namespaced()
+                .and_then(|ns| PublicKey::from_namespaced(&ns).ok());
+
+            if let Some(remote) = remote {
+                updates.push(remote, RefUpdate::from(name, old, new));
                 // Returning `true` ensures the process is not aborted.
                 return true;
             }
@@ -559,3 +659,188 @@ fn ref_updates(updates: &mut Vec<RefUpdate>) -> git::raw::RemoteCallbacks<'_> {
     });
     callbacks
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use radicle::crypto::Signer;
+    use radicle::storage::SignRepository;
+
+    use super::*;
+    use crate::crypto::test::signer::MockSigner;
+    use crate::test::{arbitrary, fixtures};
+
+    /// Create a stored repository whose signed refs were updated once after it was
+    /// initialized. Returns the first signed refs.
+    fn stored(path: &Path, signer: &MockSigner) -> (Storage, Id, git::Oid) {
+        let storage = Storage::open(path.join("storage")).unwrap();
+        let (rid, _, _, head) = fixtures::project(path.join("working"), &storage, signer).unwrap();
+        let repo = storage.repository(rid).unwrap();
+        let remote = signer.public_key();
+        let first = repo
+            .reference_oid(remote, &git::refs::storage::SIGREFS_BRANCH)
+            .unwrap();
+        let dev = git::refs::storage::branch_of(remote, &git::refname!("dev")).to_ref_string();
+
+        repo.raw().reference(dev.as_str(), head, false, "").unwrap();
+        repo.sign_refs(signer).unwrap();
+
+        (storage, rid, first)
+    }
+
+    /// The signed refs ref of the given remote.
+    fn sigrefs(remote: &PublicKey) -> Namespaced<'static> {
+        git::refs::storage::SIGREFS_BRANCH
+            .with_namespace(remote.into())
+            .to_owned()
+    }
+
+    #[test]
+    fn test_stale_sigrefs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let signer = MockSigner::default();
+        let remote = *signer.public_key();
+        let (storage, rid, first) = stored(tmp.path(), &signer);
+        let staging = StagingPhaseInitial::new(
+            &storage,
+            rid,
+            arbitrary::gen::<NodeId>(1),
+            Namespaces::All,
+            HashSet::new(),
+        )
+        .unwrap();
+
+        // The remote replays its previous signed refs.
+        staging
+            .repo
+            .raw()
+            .reference(
+                sigrefs(&remote).to_ref_string().as_str(),
+                first.into(),
+                true,
+                "",
+            )
+            .unwrap();
+
+        let staging = staging
+            .into_final(BTreeSet::from([sigrefs(&remote)]))
+            .unwrap();
+        let verifications = staging
+            .verify(Some(&storage.repository(rid).unwrap()))
+            .unwrap();
+        assert!(matches!(
+            verifications[&remote],
+            VerifiedRemote::Stale { local, staging: fetched } if fetched == first && local != first
+        ));
+
+        let (updates, _, validations) = staging.transfer(&mut RepositoryCache::default()).unwrap();
+        assert!(updates.is_empty());
+        assert!(matches!(
+            validations.as_slice(),
+            [Validation { remote: r, severity: Severity::Warning, .. }] if *r == remote
+        ));
+    }
+
+    #[test]
+    fn test_diverged_sigrefs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let signer = MockSigner::default();
+        let remote = *signer.public_key();
+        let (storage, rid, first) = stored(tmp.path(), &signer);
+        let staging = StagingPhaseInitial::new(
+            &storage,
+            rid,
+            arbitrary::gen::<NodeId>(1),
+            Namespaces::All,
+            HashSet::new(),
+        )
+        .unwrap();
+
+        // The remote signs other refs on top of its previous signed refs.
+        {
+            let raw = staging.repo.raw();
+            let mut config = raw.config().unwrap();
+            let head = staging
+                .repo
+                .reference_oid(&remote, &git::qualified!("refs/heads/master"))
+                .unwrap();
+            let feature =
+                git::refs::storage::branch_of(&remote, &git::refname!("feature")).to_ref_string();
+
+            config.set_str("user.name", "radicle").unwrap();
+            config.set_str("user.email", "radicle@localhost").unwrap();
+            raw.reference(
+                sigrefs(&remote).to_ref_string().as_str(),
+                first.into(),
+                true,
+                "",
+            )
+            .unwrap();
+            raw.reference(feature.as_str(), head.into(), false, "")
+                .unwrap();
+            staging.repo.sign_refs(&signer).unwrap();
+        }
+
+        let staging = staging
+            .into_final(BTreeSet::from([sigrefs(&remote)]))
+            .unwrap();
+        let verifications = staging
+            .verify(Some(&storage.repository(rid).unwrap()))
+            .unwrap();
+        assert!(matches!(
+            verifications[&remote],
+            VerifiedRemote::Failed { .. }
+        ));
+
+        let (updates, _, validations) = staging.transfer(&mut RepositoryCache::default()).unwrap();
+        assert!(updates.is_empty());
+        assert!(matches!(
+            validations.as_slice(),
+            [Validation { remote: r, severity: Severity::Error, .. }] if *r == remote
+        ));
+    }
+
+    #[test]
+    fn test_updates() {
+        let alice = arbitrary::gen::<PublicKey>(1);
+        let bob = arbitrary::gen::<PublicKey>(1);
+        let zero = git::raw::Oid::zero();
+        let a = git::raw::Oid::from_str("d96f425412c9f8ad5d9a9a05c9831d0728e2338d").unwrap();
+        let b = git::raw::Oid::from_str("ef4e2d0a1ee0c1c6e1a7e0cbfe0e7c6e53f9bc7b").unwrap();
+        let name = |remote: &PublicKey, branch: git::RefString| {
+            git::refs::storage::branch_of(remote, &branch).to_ref_string()
+        };
+        let mut updates = Updates::default();
+
+        updates.push(
+            alice,
+            RefUpdate::from(name(&alice, git::refname!("master")), zero, a),
+        );
+        updates.push(
+            alice,
+            RefUpdate::from(name(&alice, git::refname!("dev")), a, b),
+        );
+        updates.push(
+            bob,
+            RefUpdate::from(name(&bob, git::refname!("master")), b, zero),
+        );
+        updates.push(bob, RefUpdate::from(name(&bob, git::refname!("dev")), a, a));
+
+        assert_eq!(updates.refs.len(), 4);
+        assert_eq!(updates.by_namespace.len(), 2);
+        assert_eq!(updates.by_namespace[&alice], updates.refs[..2]);
+        assert_eq!(updates.by_namespace[&bob], updates.refs[2..]);
+
+        let (created, updated, deleted) = Updates::categorize(&updates.refs);
+        assert_eq!(created, vec![&updates.refs[0]]);
+        assert_eq!(updated, vec![&updates.refs[1]]);
+        assert_eq!(deleted, vec![&updates.refs[2]]);
+
+        let (created, updated, deleted) = Updates::categorize(&updates.by_namespace[&bob]);
+        assert!(created.is_empty());
+        assert!(updated.is_empty());
+        assert_eq!(deleted.len(), 1);
+    }
+}
@@ -13,7 +13,8 @@ use tuirealm::application::PollStrategy;
 use tuirealm::{Application, Frame, NoUserEvent};
 
 use radicle_tui::ui::context::Context;
-use radicle_tui::ui::theme::{self, Theme};
+use radicle_tui::ui::keymap::Keymap;
+use radicle_tui::ui::theme::Theme;
 use radicle_tui::Tui;
 use radicle_tui::{cob, ui};
 
@@ -90,11 +91,11 @@ pub struct App {
 /// Creates a new application using a tui-realm-application, mounts all
 /// components and sets focus to a default one.
 impl App {
-    pub fn new(profile: Profile, id: Id, project: Project) -> Self {
+    pub fn new(profile: Profile, id: Id, project: Project, theme: Theme, keymap: Keymap) -> Self {
         Self {
-            context: Context::new(profile, id, project),
+            context: Context::new(profile, id, project).with_keymap(keymap),
             pages: PageStack::default(),
-            theme: theme::default_dark(),
+            theme,
             quit: false,
         }
     }
@@ -157,7 +158,15 @@ impl Tui<Cid, Message> for App {
 
         // Add global key listener and subscribe to key events
         let global = ui::widget::common::global_listener().to_boxed();
-        app.mount(Cid::GlobalListener, global, subscription::global())?;
+        let filterable = vec![
+            Cid::Home(HomeCid::IssueBrowser),
+            Cid::Home(HomeCid::PatchBrowser),
+        ];
+        app.mount(
+            Cid::GlobalListener,
+            global,
+            subscription::global(filterable),
+        )?;
 
         Ok(())
     }
@@ -171,7 +180,7 @@ impl Tui<Cid, Message> for App {
     fn update(&mut self, app: &mut Application<Cid, Message, NoUserEvent>) -> Result<bool> {
         match app.tick(PollStrategy::Once) {
             Ok(messages) if !messages.is_empty() => {
-                let theme = theme::default_dark();
+                let theme = self.theme.clone();
                 for message in messages {
                     match message {
                         Message::Issue(IssueMessage::Show(id)) => {
@@ -1,10 +1,11 @@
 use tuirealm::command::{Cmd, CmdResult, Direction as MoveDirection};
 use tuirealm::event::{Event, Key, KeyEvent};
+use tuirealm::props::{AttrValue, Attribute};
 use tuirealm::{MockComponent, NoUserEvent, State, StateValue};
 
 use radicle_tui::ui::widget::common::container::{GlobalListener, LabeledContainer, Tabs};
 use radicle_tui::ui::widget::common::context::{ContextBar, Shortcuts};
-use radicle_tui::ui::widget::common::list::PropertyList;
+use radicle_tui::ui::widget::common::list::{self, PropertyList};
 use radicle_tui::ui::widget::home::{Dashboard, IssueBrowser, PatchBrowser};
 use radicle_tui::ui::widget::{issue, patch};
 
@@ -12,6 +13,20 @@ use radicle_tui::ui::widget::Widget;
 
 use super::{IssueMessage, Message, PatchMessage};
 
+/// Returns the command that edits a table filter for the given event, if any.
+fn filter_cmd(event: &Event<NoUserEvent>) -> Option<Cmd> {
+    match event {
+        Event::Keyboard(KeyEvent { code, .. }) => match code {
+            Key::Char(c) => Some(Cmd::Type(*c)),
+            Key::Backspace => Some(Cmd::Delete),
+            Key::Enter => Some(Cmd::Custom(list::CMD_FILTER_APPLY)),
+            Key::Esc => Some(Cmd::Cancel),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Since the framework does not know the type of messages that are being
 /// passed around in the app, the following handlers need to be implemented for
 /// each component used.
@@ -40,6 +55,14 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<Tabs> {
                     _ => None,
                 }
             }
+            Event::Keyboard(KeyEvent {
+                code: Key::BackTab, ..
+            }) => match self.perform(Cmd::Move(MoveDirection::Left)) {
+                CmdResult::Changed(State::One(StateValue::U16(index))) => {
+                    Some(Message::NavigationChanged(index))
+                }
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -51,21 +74,9 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<issue::LargeList> {
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Message::Issue(IssueMessage::Leave))
             }
-            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
-                let result = self.perform(Cmd::Move(MoveDirection::Up));
-                match result {
-                    CmdResult::Changed(State::One(StateValue::Usize(selected))) => {
-                        let item = self.items().get(selected)?;
-                        Some(Message::Issue(IssueMessage::Changed(item.id().to_owned())))
-                    }
-                    _ => None,
-                }
-            }
-            Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
-            }) => {
-                let result = self.perform(Cmd::Move(MoveDirection::Down));
-                match result {
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let cmd = self.keymap().navigation(&code)?;
+                match self.perform(cmd) {
                     CmdResult::Changed(State::One(StateValue::Usize(selected))) => {
                         let item = self.items().get(selected)?;
                         Some(Message::Issue(IssueMessage::Changed(item.id().to_owned())))
@@ -86,15 +97,52 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<issue::Details> {
 
 impl tuirealm::Component<Message, NoUserEvent> for Widget<PatchBrowser> {
     fn on(&mut self, event: Event<NoUserEvent>) -> Option<Message> {
+        if self.is_filtering() {
+            if let Some(cmd) = filter_cmd(&event) {
+                self.perform(cmd);
+                self.attr(
+                    Attribute::Custom(list::ATTR_FILTERING),
+                    AttrValue::Flag(self.is_filtering()),
+                );
+                return Some(Message::Tick);
+            }
+        }
+
         match event {
-            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
-                self.perform(Cmd::Move(MoveDirection::Up));
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('/'),
+                ..
+            }) => {
+                self.perform(Cmd::Custom(list::CMD_FILTER));
+                self.attr(
+                    Attribute::Custom(list::ATTR_FILTERING),
+                    AttrValue::Flag(true),
+                );
+                Some(Message::Tick)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                self.perform(Cmd::Cancel);
+                Some(Message::Tick)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('s'),
+                ..
+            }) => {
+                self.perform(Cmd::Custom(list::CMD_SORT_COLUMN));
                 Some(Message::Tick)
             }
             Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
+                code: Key::Char('o'),
+                ..
             }) => {
-                self.perform(Cmd::Move(MoveDirection::Down));
+                self.perform(Cmd::Custom(list::CMD_SORT_ORDER));
+                Some(Message::Tick)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(' '),
+                ..
+            }) => {
+                self.perform(Cmd::Custom(list::CMD_TOGGLE_MARK));
                 Some(Message::Tick)
             }
             Event::Keyboard(KeyEvent {
@@ -102,13 +150,18 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<PatchBrowser> {
             }) => {
                 let result = self.perform(Cmd::Submit);
                 match result {
-                    CmdResult::Submit(State::One(StateValue::Usize(selected))) => {
-                        let item = self.items().get(selected)?;
+                    CmdResult::Submit(_) => {
+                        let item = self.selected_item()?;
                         Some(Message::Patch(PatchMessage::Show(item.id().to_owned())))
                     }
                     _ => None,
                 }
             }
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let cmd = self.keymap().navigation(&code)?;
+                self.perform(cmd);
+                Some(Message::Tick)
+            }
             _ => None,
         }
     }
@@ -116,15 +169,52 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<PatchBrowser> {
 
 impl tuirealm::Component<Message, NoUserEvent> for Widget<IssueBrowser> {
     fn on(&mut self, event: Event<NoUserEvent>) -> Option<Message> {
+        if self.is_filtering() {
+            if let Some(cmd) = filter_cmd(&event) {
+                self.perform(cmd);
+                self.attr(
+                    Attribute::Custom(list::ATTR_FILTERING),
+                    AttrValue::Flag(self.is_filtering()),
+                );
+                return Some(Message::Tick);
+            }
+        }
+
         match event {
-            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
-                self.perform(Cmd::Move(MoveDirection::Up));
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('/'),
+                ..
+            }) => {
+                self.perform(Cmd::Custom(list::CMD_FILTER));
+                self.attr(
+                    Attribute::Custom(list::ATTR_FILTERING),
+                    AttrValue::Flag(true),
+                );
+                Some(Message::Tick)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                self.perform(Cmd::Cancel);
                 Some(Message::Tick)
             }
             Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
+                code: Key::Char('s'),
+                ..
+            }) => {
+                self.perform(Cmd::Custom(list::CMD_SORT_COLUMN));
+                Some(Message::Tick)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('o'),
+                ..
+            }) => {
+                self.perform(Cmd::Custom(list::CMD_SORT_ORDER));
+                Some(Message::Tick)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(' '),
+                ..
             }) => {
-                self.perform(Cmd::Move(MoveDirection::Down));
+                self.perform(Cmd::Custom(list::CMD_TOGGLE_MARK));
                 Some(Message::Tick)
             }
             Event::Keyboard(KeyEvent {
@@ -132,13 +222,18 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<IssueBrowser> {
             }) => {
                 let result = self.perform(Cmd::Submit);
                 match result {
-                    CmdResult::Submit(State::One(StateValue::Usize(selected))) => {
-                        let item = self.items().get(selected)?;
+                    CmdResult::Submit(_) => {
+                        let item = self.selected_item()?;
                         Some(Message::Issue(IssueMessage::Show(item.id().to_owned())))
                     }
                     _ => None,
                 }
             }
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let cmd = self.keymap().navigation(&code)?;
+                self.perform(cmd);
+                Some(Message::Tick)
+            }
             _ => None,
         }
     }
@@ -167,6 +262,15 @@ impl tuirealm::Component<Message, NoUserEvent> for Widget<patch::Files> {
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Message::Patch(PatchMessage::Leave))
             }
+            Event::Keyboard(KeyEvent { code, .. }) => {
+                let cmd = match code {
+                    Key::Left => Cmd::Move(MoveDirection::Left),
+                    Key::Right => Cmd::Move(MoveDirection::Right),
+                    code => self.keymap().navigation(&code)?,
+                };
+                self.perform(cmd);
+                Some(Message::Tick)
+            }
             _ => None,
         }
     }
@@ -129,6 +129,10 @@ impl ViewPage for HomeView {
             &Cid::Home(HomeCid::Navigation),
             Sub::new(subscription::navigation_clause(), SubClause::Always),
         )?;
+        app.subscribe(
+            &Cid::Home(HomeCid::Navigation),
+            Sub::new(subscription::back_navigation_clause(), SubClause::Always),
+        )?;
 
         Ok(())
     }
@@ -138,6 +142,10 @@ impl ViewPage for HomeView {
             &Cid::Home(HomeCid::Navigation),
             subscription::navigation_clause(),
         )?;
+        app.unsubscribe(
+            &Cid::Home(HomeCid::Navigation),
+            subscription::back_navigation_clause(),
+        )?;
 
         Ok(())
     }
@@ -260,7 +268,7 @@ impl ViewPage for PatchView {
         let (id, patch) = &self.patch;
         let navigation = widget::patch::navigation(theme).to_boxed();
         let activity = widget::patch::activity(theme, (*id, patch), context.profile()).to_boxed();
-        let files = widget::patch::files(theme, (*id, patch), context.profile()).to_boxed();
+        let files = widget::patch::files(context, theme, (*id, patch)).to_boxed();
 
         app.remount(Cid::Patch(PatchCid::Navigation), navigation, vec![])?;
         app.remount(Cid::Patch(PatchCid::Activity), activity, vec![])?;
@@ -306,6 +314,10 @@ impl ViewPage for PatchView {
             &Cid::Patch(PatchCid::Navigation),
             Sub::new(subscription::navigation_clause(), SubClause::Always),
         )?;
+        app.subscribe(
+            &Cid::Patch(PatchCid::Navigation),
+            Sub::new(subscription::back_navigation_clause(), SubClause::Always),
+        )?;
 
         Ok(())
     }
@@ -315,6 +327,10 @@ impl ViewPage for PatchView {
             &Cid::Patch(PatchCid::Navigation),
             subscription::navigation_clause(),
         )?;
+        app.unsubscribe(
+            &Cid::Patch(PatchCid::Navigation),
+            subscription::back_navigation_clause(),
+        )?;
 
         Ok(())
     }
@@ -1,8 +1,11 @@
 use std::hash::Hash;
 
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+use tuirealm::props::{AttrValue, Attribute};
 use tuirealm::{Sub, SubClause, SubEventClause};
 
+use radicle_tui::ui::widget::common::list;
+
 pub fn navigation_clause<UserEvent>() -> SubEventClause<UserEvent>
 where
     UserEvent: Clone + Eq + PartialEq + PartialOrd,
@@ -13,7 +16,40 @@ where
     })
 }
 
-pub fn global<Id, UserEvent>() -> Vec<Sub<Id, UserEvent>>
+pub fn back_navigation_clause<UserEvent>() -> SubEventClause<UserEvent>
+where
+    UserEvent: Clone + Eq + PartialEq + PartialOrd,
+{
+    SubEventClause::Keyboard(KeyEvent {
+        code: Key::BackTab,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+/// Holds unless one of the given components is editing its filter, in which case
+/// key presses are typed into the filter instead.
+pub fn not_filtering<Id>(ids: Vec<Id>) -> SubClause<Id>
+where
+    Id: Clone + Hash + Eq + PartialEq,
+{
+    let filtering = ids
+        .into_iter()
+        .map(|id| {
+            SubClause::HasAttrValue(
+                id,
+                Attribute::Custom(list::ATTR_FILTERING),
+                AttrValue::Flag(true),
+            )
+        })
+        .reduce(|a, b| SubClause::Or(Box::new(a), Box::new(b)));
+
+    match filtering {
+        Some(filtering) => SubClause::Not(Box::new(filtering)),
+        None => SubClause::Always,
+    }
+}
+
+pub fn global<Id, UserEvent>(filterable: Vec<Id>) -> Vec<Sub<Id, UserEvent>>
 where
     Id: Clone + Hash + Eq + PartialEq,
     UserEvent: Clone + Eq + PartialEq + PartialOrd,
@@ -24,7 +60,7 @@ where
                 code: Key::Char('q'),
                 modifiers: KeyModifiers::NONE,
             }),
-            SubClause::Always,
+            not_filtering(filterable),
         ),
         Sub::new(SubEventClause::WindowResize, SubClause::Always),
     ]
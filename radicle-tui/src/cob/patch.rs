@@ -1,15 +1,17 @@
 use anyhow::Result;
 
-use radicle::cob::patch::{Patch, PatchId, Patches};
+use radicle::cob::patch::{Patch, PatchId, Patches, Status};
 use radicle::storage::git::Repository;
 
 pub fn all(repository: &Repository) -> Result<Vec<(PatchId, Patch)>> {
-    let patches = Patches::open(repository)?
-        .all()
-        .map(|iter| iter.flatten().collect::<Vec<_>>())?;
+    filtered(repository, None)
+}
+
+pub fn filtered(repository: &Repository, status: Option<Status>) -> Result<Vec<(PatchId, Patch)>> {
+    let patches = Patches::open(repository)?;
 
     Ok(patches
-        .into_iter()
+        .filtered(status)?
         .map(|(id, patch, _)| (id, patch))
         .collect::<Vec<_>>())
 }
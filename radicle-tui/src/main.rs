@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::process;
 
 use anyhow::{anyhow, Context};
@@ -6,6 +7,8 @@ use radicle::storage::ReadStorage;
 
 use radicle_cli as cli;
 use radicle_term as term;
+use radicle_tui::ui::keymap::Keymap;
+use radicle_tui::ui::theme::{self, Theme};
 use radicle_tui::Window;
 
 mod app;
@@ -22,21 +25,37 @@ Usage
 
 Options
 
-    --version       Print version
-    --help          Print help
+    --theme <path>      Load the theme from a JSON file
+    --keymap <keymap>   Keys to navigate lists with: `default` or `vim` (default: default)
+    --version           Print version
+    --help              Print help
 
 "#;
 
-struct Options;
+struct Options {
+    theme: Option<PathBuf>,
+    keymap: Keymap,
+}
 
 impl Options {
     fn from_env() -> Result<Self, anyhow::Error> {
         use lexopt::prelude::*;
 
         let mut parser = lexopt::Parser::from_env();
+        let mut theme = None;
+        let mut keymap = Keymap::default();
 
         while let Some(arg) = parser.next()? {
             match arg {
+                Long("theme") => {
+                    theme = Some(parser.value()?.into());
+                }
+                Long("keymap") => {
+                    let value = parser.value()?;
+                    let value = value.to_string_lossy();
+
+                    keymap = value.parse().map_err(|e: String| anyhow!(e))?;
+                }
                 Long("version") => {
                     println!("{NAME} {VERSION}+{GIT_HEAD}");
                     process::exit(0);
@@ -49,12 +68,12 @@ impl Options {
             }
         }
 
-        Ok(Self {})
+        Ok(Self { theme, keymap })
     }
 }
 
 fn execute() -> anyhow::Result<()> {
-    let _ = Options::from_env()?;
+    let options = Options::from_env()?;
 
     let (_, id) = radicle::rad::cwd()
         .map_err(|_| anyhow!("this command must be run in the context of a project"))?;
@@ -69,9 +88,17 @@ fn execute() -> anyhow::Result<()> {
         .context("No project with such `id` exists")?;
 
     let project = payload.project()?;
+    let theme = match options.theme {
+        Some(path) => Theme::from_file(&path)
+            .with_context(|| format!("Failed to load theme from {}", path.display()))?,
+        None => theme::from_env(),
+    };
 
     let mut window = Window::default();
-    window.run(&mut app::App::new(profile, id, project), 1000 / FPS)?;
+    window.run(
+        &mut app::App::new(profile, id, project, theme, options.keymap),
+        1000 / FPS,
+    )?;
 
     Ok(())
 }
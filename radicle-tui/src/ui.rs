@@ -1,6 +1,7 @@
 pub mod cob;
 pub mod context;
 pub mod ext;
+pub mod keymap;
 pub mod layout;
 pub mod state;
 pub mod theme;
@@ -1,8 +1,14 @@
+use std::cell::OnceCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
 use radicle_surf;
+use radicle_surf::diff::{Diff, DiffContent, DiffFile, FileDiff, Modification};
 
 use cli::terminal::format;
 use radicle_cli as cli;
 
+use radicle::git;
 use radicle::prelude::Did;
 use radicle::storage::git::Repository;
 use radicle::storage::{Oid, ReadRepository};
@@ -12,7 +18,7 @@ use radicle::cob::issue::{Issue, IssueId, State as IssueState};
 use radicle::cob::patch::{Patch, PatchId, State as PatchState};
 use radicle::cob::{Tag, Timestamp};
 
-use tuirealm::props::{Color, Style};
+use tuirealm::props::{Color, Style, TextModifiers};
 use tuirealm::tui::text::{Span, Spans};
 use tuirealm::tui::widgets::Cell;
 
@@ -59,10 +65,8 @@ pub struct PatchItem {
     author: AuthorItem,
     /// Head of the latest revision.
     head: Oid,
-    /// Lines added by the latest revision.
-    added: u16,
-    /// Lines removed by the latest revision.
-    removed: u16,
+    /// Lines added and removed by the latest revision.
+    stats: DiffStats,
     /// Time when patch was opened.
     timestamp: Timestamp,
 }
@@ -88,12 +92,14 @@ impl PatchItem {
         &self.head
     }
 
-    pub fn added(&self) -> u16 {
-        self.added
+    /// Lines added by the latest revision. Computes the diff on first access.
+    pub fn added(&self) -> Option<u16> {
+        self.stats.get().map(|(added, _)| added)
     }
 
-    pub fn removed(&self) -> u16 {
-        self.removed
+    /// Lines removed by the latest revision. Computes the diff on first access.
+    pub fn removed(&self) -> Option<u16> {
+        self.stats.get().map(|(_, removed)| removed)
     }
 
     pub fn timestamp(&self) -> &Timestamp {
@@ -101,18 +107,26 @@ impl PatchItem {
     }
 }
 
-impl TryFrom<(&Profile, &Repository, PatchId, Patch)> for PatchItem {
-    type Error = anyhow::Error;
-
-    fn try_from(value: (&Profile, &Repository, PatchId, Patch)) -> Result<Self, Self::Error> {
+impl
+    From<(
+        &Profile,
+        &Option<Rc<radicle_surf::Repository>>,
+        PatchId,
+        Patch,
+    )> for PatchItem
+{
+    fn from(
+        value: (
+            &Profile,
+            &Option<Rc<radicle_surf::Repository>>,
+            PatchId,
+            Patch,
+        ),
+    ) -> Self {
         let (profile, repo, id, patch) = value;
         let (_, rev) = patch.latest();
-        let repo = radicle_surf::Repository::open(repo.path())?;
-        let base = repo.commit(rev.base())?;
-        let head = repo.commit(rev.head())?;
-        let diff = repo.diff(base.id, head.id)?;
 
-        Ok(PatchItem {
+        PatchItem {
             id,
             state: patch.state().clone(),
             title: patch.title().into(),
@@ -121,16 +135,66 @@ impl TryFrom<(&Profile, &Repository, PatchId, Patch)> for PatchItem {
                 is_you: *patch.author().id == *profile.did(),
             },
             head: rev.head(),
-            added: diff.stats().insertions as u16,
-            removed: diff.stats().deletions as u16,
+            stats: DiffStats::new(repo.clone(), *rev.base(), rev.head()),
             timestamp: rev.timestamp(),
-        })
+        }
+    }
+}
+
+/// Lines added and removed between two commits.
+///
+/// Computing them requires a diff, which is too expensive to do for every patch
+/// up front. Instead, they are computed once they're needed, ie. when the patch is
+/// displayed or sorted by them, and cached afterwards.
+///
+/// The repository is shared between items, so that it's only opened once.
+#[derive(Clone)]
+pub struct DiffStats {
+    /// Repository the commits are in. `None` if it couldn't be opened, in which case
+    /// there are no stats.
+    repo: Option<Rc<radicle_surf::Repository>>,
+    /// Commit the diff starts from.
+    base: Oid,
+    /// Commit the diff ends at.
+    head: Oid,
+    /// Lines added and removed, if the diff was computed. `None` if it failed.
+    stats: OnceCell<Option<(u16, u16)>>,
+}
+
+impl DiffStats {
+    pub fn new(repo: Option<Rc<radicle_surf::Repository>>, base: Oid, head: Oid) -> Self {
+        Self {
+            repo,
+            base,
+            head,
+            stats: OnceCell::new(),
+        }
+    }
+
+    /// Returns the lines added and removed, computing them on first access.
+    pub fn get(&self) -> Option<(u16, u16)> {
+        *self.stats.get_or_init(|| self.diff().ok())
+    }
+
+    fn diff(&self) -> anyhow::Result<(u16, u16)> {
+        let repo = self
+            .repo
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("repository could not be opened"))?;
+        let base = repo.commit(self.base)?;
+        let head = repo.commit(self.head)?;
+        let diff = repo.diff(base.id, head.id)?;
+
+        Ok((
+            diff.stats().insertions as u16,
+            diff.stats().deletions as u16,
+        ))
     }
 }
 
 impl TableItem<8> for PatchItem {
     fn row(&self, theme: &Theme) -> [Cell; 8] {
-        let (icon, color) = format_patch_state(&self.state);
+        let (icon, color) = format_patch_state(&self.state, theme);
         let state = Cell::from(icon).style(Style::default().fg(color));
 
         let id = Cell::from(format::cob(&self.id))
@@ -145,10 +209,10 @@ impl TableItem<8> for PatchItem {
         let head = Cell::from(format::oid(self.head).item)
             .style(Style::default().fg(theme.colors.browser_patch_list_head));
 
-        let added = Cell::from(format!("{}", self.added))
+        let added = Cell::from(format_stat(self.added()))
             .style(Style::default().fg(theme.colors.browser_patch_list_added));
 
-        let removed = Cell::from(format!("{}", self.removed))
+        let removed = Cell::from(format_stat(self.removed()))
             .style(Style::default().fg(theme.colors.browser_patch_list_removed));
 
         let updated = Cell::from(format::timestamp(&self.timestamp).to_string())
@@ -156,6 +220,32 @@ impl TableItem<8> for PatchItem {
 
         [state, id, title, author, head, added, removed, updated]
     }
+
+    fn compare(&self, other: &Self, column: usize) -> Ordering {
+        match column {
+            0 => self.state.status().cmp(&other.state.status()),
+            1 => self.id.cmp(&other.id),
+            2 => self.title.cmp(&other.title),
+            3 => compare_authors(&self.author, &other.author),
+            4 => self.head.cmp(&other.head),
+            5 => self.added().cmp(&other.added()),
+            6 => self.removed().cmp(&other.removed()),
+            7 => self.timestamp.cmp(&other.timestamp),
+            _ => Ordering::Equal,
+        }
+    }
+
+    fn filter_text(&self) -> String {
+        // Includes the full DID, since the displayed one is abbreviated and authors are
+        // usually looked up by the first characters of their key.
+        format!(
+            "{} {} {} {}",
+            self.id,
+            self.title,
+            format_author(&self.author.did, self.author.is_you),
+            self.author.did,
+        )
+    }
 }
 
 /// An issue item that can be used in tables, list or trees.
@@ -237,7 +327,7 @@ impl From<(&Profile, &Repository, IssueId, Issue)> for IssueItem {
 
 impl TableItem<7> for IssueItem {
     fn row(&self, theme: &Theme) -> [Cell; 7] {
-        let (icon, color) = format_issue_state(&self.state);
+        let (icon, color) = format_issue_state(&self.state, theme);
         let state = Cell::from(icon).style(Style::default().fg(color));
 
         let id = Cell::from(format::cob(&self.id))
@@ -265,11 +355,37 @@ impl TableItem<7> for IssueItem {
 
         [state, id, title, author, tags, assignees, opened]
     }
+
+    fn compare(&self, other: &Self, column: usize) -> Ordering {
+        match column {
+            0 => self.state.cmp(&other.state),
+            1 => self.id.cmp(&other.id),
+            2 => self.title.cmp(&other.title),
+            3 => compare_authors(&self.author, &other.author),
+            4 => self.tags.cmp(&other.tags),
+            5 => self
+                .assignees
+                .iter()
+                .map(|a| a.did.to_string())
+                .cmp(other.assignees.iter().map(|a| a.did.to_string())),
+            6 => self.timestamp.cmp(&other.timestamp),
+            _ => Ordering::Equal,
+        }
+    }
+
+    fn filter_text(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.id,
+            self.title,
+            format_author(&self.author.did, self.author.is_you)
+        )
+    }
 }
 
 impl ListItem for IssueItem {
     fn row(&self, theme: &Theme) -> tuirealm::tui::widgets::ListItem {
-        let (state, state_color) = format_issue_state(&self.state);
+        let (state, state_color) = format_issue_state(&self.state, theme);
         let lines = vec![
             Spans::from(vec![
                 Span::styled(state, Style::default().fg(state_color)),
@@ -304,18 +420,267 @@ impl PartialEq for IssueItem {
     }
 }
 
-pub fn format_patch_state(state: &PatchState) -> (String, Color) {
+/// A line of a patch diff that can be used in lists.
+#[derive(Clone, PartialEq, Eq)]
+pub enum DiffItem {
+    /// Header of a changed file, with its number of added and deleted lines.
+    File {
+        header: String,
+        additions: usize,
+        deletions: usize,
+    },
+    /// Header of a hunk.
+    Hunk(String),
+    /// An added line, with its line number in the new file.
+    Addition { line_no: u32, content: String },
+    /// A deleted line, with its line number in the old file.
+    Deletion { line_no: u32, content: String },
+    /// An unchanged line, with its line numbers in the old and new file.
+    Context {
+        line_no_old: u32,
+        line_no_new: u32,
+        content: String,
+    },
+    /// A note in place of content that can't be displayed.
+    Note(String),
+}
+
+impl DiffItem {
+    /// Returns the diff between the base and head of the latest patch revision.
+    pub fn all(repo: &Repository, patch: &Patch) -> anyhow::Result<Vec<Self>> {
+        let (_, rev) = patch.latest();
+        let repo = radicle_surf::Repository::open(repo.path())?;
+        let base = repo.commit(rev.base())?;
+        let head = repo.commit(rev.head())?;
+        let diff = repo.diff(base.id, head.id)?;
+
+        Ok(Self::from_diff(&diff, repo.raw()))
+    }
+
+    /// Returns the lines of the given diff. Blobs are looked up in the given repository
+    /// to show the size of binary files.
+    pub fn from_diff(diff: &Diff, repo: &git::raw::Repository) -> Vec<Self> {
+        let mut items = vec![];
+
+        for file in diff.files() {
+            let (header, content, old, new) = match file {
+                FileDiff::Modified(f) => (
+                    format!("{}", f.path.display()),
+                    &f.diff,
+                    Some(&f.old),
+                    Some(&f.new),
+                ),
+                FileDiff::Added(f) => (
+                    format!("{} (added)", f.path.display()),
+                    &f.diff,
+                    None,
+                    Some(&f.new),
+                ),
+                FileDiff::Deleted(f) => (
+                    format!("{} (deleted)", f.path.display()),
+                    &f.diff,
+                    Some(&f.old),
+                    None,
+                ),
+                FileDiff::Moved(f) => {
+                    items.push(Self::File {
+                        header: format!(
+                            "{} -> {} (moved)",
+                            f.old_path.display(),
+                            f.new_path.display()
+                        ),
+                        additions: 0,
+                        deletions: 0,
+                    });
+                    continue;
+                }
+                // Copies are not detected when diffing.
+                FileDiff::Copied(_) => continue,
+            };
+            let (additions, deletions) = match content {
+                DiffContent::Plain { hunks, .. } => hunks
+                    .iter()
+                    .flat_map(|hunk| hunk.lines.iter())
+                    .fold((0, 0), |(a, d), line| match line {
+                        Modification::Addition(_) => (a + 1, d),
+                        Modification::Deletion(_) => (a, d + 1),
+                        Modification::Context { .. } => (a, d),
+                    }),
+                _ => (0, 0),
+            };
+            items.push(Self::File {
+                header,
+                additions,
+                deletions,
+            });
+
+            match content {
+                DiffContent::Plain { hunks, .. } => {
+                    for hunk in hunks.iter() {
+                        items.push(Self::Hunk(
+                            hunk.header.from_utf8_lossy().trim_end().to_owned(),
+                        ));
+                        items.extend(hunk.lines.iter().map(Self::from));
+                    }
+                }
+                DiffContent::Binary => items.push(Self::Note(binary_note(repo, old, new))),
+                DiffContent::Empty => items.push(Self::Note(String::from("empty file"))),
+            }
+        }
+        items
+    }
+
+    pub fn is_file(&self) -> bool {
+        matches!(self, Self::File { .. })
+    }
+}
+
+/// Describes a binary file change by the sizes of the old and new blob, eg.
+/// `binary file, 1.2 KiB -> 4.5 KiB`.
+fn binary_note(
+    repo: &git::raw::Repository,
+    old: Option<&DiffFile>,
+    new: Option<&DiffFile>,
+) -> String {
+    let size = |file: &DiffFile| {
+        repo.find_blob(*file.oid)
+            .map(|blob| format::bytes(blob.size()))
+            .unwrap_or_else(|_| String::from("?"))
+    };
+
+    match (old, new) {
+        (Some(old), Some(new)) => format!("binary file, {} -> {}", size(old), size(new)),
+        (Some(file), None) | (None, Some(file)) => format!("binary file, {}", size(file)),
+        (None, None) => String::from("binary file"),
+    }
+}
+
+impl From<&Modification> for DiffItem {
+    fn from(modification: &Modification) -> Self {
+        match modification {
+            Modification::Addition(a) => Self::Addition {
+                line_no: a.line_no,
+                content: a.line.from_utf8_lossy().trim_end().to_owned(),
+            },
+            Modification::Deletion(d) => Self::Deletion {
+                line_no: d.line_no,
+                content: d.line.from_utf8_lossy().trim_end().to_owned(),
+            },
+            Modification::Context {
+                line,
+                line_no_old,
+                line_no_new,
+            } => Self::Context {
+                line_no_old: *line_no_old,
+                line_no_new: *line_no_new,
+                content: line.from_utf8_lossy().trim_end().to_owned(),
+            },
+        }
+    }
+}
+
+impl ListItem for DiffItem {
+    fn row(&self, theme: &Theme) -> tuirealm::tui::widgets::ListItem {
+        let line_no = |n: Option<u32>| {
+            Span::styled(
+                format!("{:>5}", n.map(|n| n.to_string()).unwrap_or_default()),
+                Style::default().fg(theme.colors.browser_list_timestamp),
+            )
+        };
+        let line = |old: Option<u32>, new: Option<u32>, sign: char, content: &str, color| {
+            Spans::from(vec![
+                line_no(old),
+                line_no(new),
+                Span::raw(" "),
+                Span::styled(format!("{sign} {content}"), Style::default().fg(color)),
+            ])
+        };
+        let spans = match self {
+            Self::File {
+                header,
+                additions,
+                deletions,
+            } => {
+                let mut spans = vec![Span::styled(
+                    header.clone(),
+                    Style::default()
+                        .fg(theme.colors.default_fg)
+                        .add_modifier(TextModifiers::BOLD),
+                )];
+                if *additions > 0 {
+                    spans.push(Span::styled(
+                        format!(" +{additions}"),
+                        Style::default().fg(theme.colors.browser_patch_list_added),
+                    ));
+                }
+                if *deletions > 0 {
+                    spans.push(Span::styled(
+                        format!(" -{deletions}"),
+                        Style::default().fg(theme.colors.browser_patch_list_removed),
+                    ));
+                }
+                Spans::from(spans)
+            }
+            Self::Hunk(header) => Spans::from(Span::styled(
+                header.clone(),
+                Style::default().fg(theme.colors.browser_list_id),
+            )),
+            Self::Addition { line_no, content } => line(
+                None,
+                Some(*line_no),
+                '+',
+                content,
+                theme.colors.browser_patch_list_added,
+            ),
+            Self::Deletion { line_no, content } => line(
+                Some(*line_no),
+                None,
+                '-',
+                content,
+                theme.colors.browser_patch_list_removed,
+            ),
+            Self::Context {
+                line_no_old,
+                line_no_new,
+                content,
+            } => line(
+                Some(*line_no_old),
+                Some(*line_no_new),
+                ' ',
+                content,
+                theme.colors.default_fg,
+            ),
+            Self::Note(note) => Spans::from(Span::styled(
+                note.clone(),
+                Style::default().fg(theme.colors.browser_list_description),
+            )),
+        };
+        tuirealm::tui::widgets::ListItem::new(spans)
+    }
+}
+
+pub fn format_patch_state(state: &PatchState, theme: &Theme) -> (String, Color) {
+    let icon = format!(" {} ", theme.icons.state);
+
     match state {
-        PatchState::Open { conflicts: _ } => (" ● ".into(), Color::Green),
-        PatchState::Archived => (" ● ".into(), Color::Yellow),
-        PatchState::Draft => (" ● ".into(), Color::Gray),
+        PatchState::Open { conflicts: _ } => (icon, Color::Green),
+        PatchState::Archived => (icon, Color::Yellow),
+        PatchState::Draft => (icon, Color::Gray),
         PatchState::Merged {
             revision: _,
             commit: _,
-        } => (" ✔ ".into(), Color::Blue),
+        } => (format!(" {} ", theme.icons.state_merged), Color::Blue),
     }
 }
 
+/// Compares authors the way they're displayed, falling back to the full DID for
+/// authors whose abbreviated DIDs are the same.
+fn compare_authors(a: &AuthorItem, b: &AuthorItem) -> Ordering {
+    format_author(&a.did, a.is_you)
+        .cmp(&format_author(&b.did, b.is_you))
+        .then_with(|| a.did.to_string().cmp(&b.did.to_string()))
+}
+
 pub fn format_author(did: &Did, is_you: bool) -> String {
     if is_you {
         format!("{} (you)", format::did(did))
@@ -324,10 +689,18 @@ pub fn format_author(did: &Did, is_you: bool) -> String {
     }
 }
 
-pub fn format_issue_state(state: &IssueState) -> (String, Color) {
+/// Formats a line count, or a placeholder if it couldn't be computed.
+pub fn format_stat(stat: Option<u16>) -> String {
+    stat.map(|stat| stat.to_string())
+        .unwrap_or_else(|| String::from("-"))
+}
+
+pub fn format_issue_state(state: &IssueState, theme: &Theme) -> (String, Color) {
+    let icon = format!(" {} ", theme.icons.state);
+
     match state {
-        IssueState::Open => (" ● ".into(), Color::Green),
-        IssueState::Closed { reason: _ } => (" ● ".into(), Color::Red),
+        IssueState::Open => (icon, Color::Green),
+        IssueState::Closed { reason: _ } => (icon, Color::Red),
     }
 }
 
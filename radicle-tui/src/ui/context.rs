@@ -1,14 +1,18 @@
+use radicle::node::tracking;
 use radicle::prelude::{Id, Project};
 use radicle::Profile;
 
 use radicle::storage::git::Repository;
 use radicle::storage::ReadStorage;
 
+use crate::ui::keymap::Keymap;
+
 pub struct Context {
     profile: Profile,
     id: Id,
     project: Project,
     repository: Repository,
+    keymap: Keymap,
 }
 
 impl Context {
@@ -19,9 +23,15 @@ impl Context {
             profile,
             project,
             repository,
+            keymap: Keymap::default(),
         }
     }
 
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
     pub fn profile(&self) -> &Profile {
         &self.profile
     }
@@ -37,4 +47,20 @@ impl Context {
     pub fn repository(&self) -> &Repository {
         &self.repository
     }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Returns the tracking policy and scope of the repository. Falls back to the
+    /// node's default policy and scope if there is no entry for the repository.
+    pub fn tracking(&self) -> Result<tracking::Repo, tracking::store::Error> {
+        let repo = self.profile.tracking()?.repo_policy(&self.id)?;
+
+        Ok(repo.unwrap_or(tracking::Repo {
+            id: self.id,
+            scope: self.profile.config.node.scope,
+            policy: self.profile.config.node.policy,
+        }))
+    }
 }
@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::event::Key;
+
+/// Keys used to navigate lists and tables.
+///
+/// Arrow, page and home/end keys are always mapped. Other key maps only add to them,
+/// as long as the added keys don't conflict with other shortcuts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Keymap {
+    /// Arrow, page and home/end keys only.
+    #[default]
+    Default,
+    /// Additionally, `j`/`k` move down and up, and `g`/`G` go to the top and bottom.
+    Vim,
+}
+
+impl Keymap {
+    /// Returns the navigation command for the given key, if any.
+    pub fn navigation(&self, key: &Key) -> Option<Cmd> {
+        let cmd = match (self, key) {
+            (_, Key::Up) | (Self::Vim, Key::Char('k')) => Cmd::Move(Direction::Up),
+            (_, Key::Down) | (Self::Vim, Key::Char('j')) => Cmd::Move(Direction::Down),
+            (_, Key::PageUp) => Cmd::Scroll(Direction::Up),
+            (_, Key::PageDown) => Cmd::Scroll(Direction::Down),
+            (_, Key::Home) | (Self::Vim, Key::Char('g')) => Cmd::GoTo(Position::Begin),
+            (_, Key::End) | (Self::Vim, Key::Char('G')) => Cmd::GoTo(Position::End),
+            _ => return None,
+        };
+        Some(cmd)
+    }
+}
+
+impl FromStr for Keymap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(Self::Default),
+            "vim" => Ok(Self::Vim),
+            _ => Err(format!("unknown keymap '{s}', expected 'default' or 'vim'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_navigation() {
+        for keymap in [Keymap::Default, Keymap::Vim] {
+            assert_eq!(
+                keymap.navigation(&Key::Down),
+                Some(Cmd::Move(Direction::Down))
+            );
+            assert_eq!(keymap.navigation(&Key::End), Some(Cmd::GoTo(Position::End)));
+        }
+        assert_eq!(Keymap::Default.navigation(&Key::Char('j')), None);
+        assert_eq!(
+            Keymap::Vim.navigation(&Key::Char('j')),
+            Some(Cmd::Move(Direction::Down))
+        );
+        assert_eq!(
+            Keymap::Vim.navigation(&Key::Char('G')),
+            Some(Cmd::GoTo(Position::End))
+        );
+        assert_eq!(Keymap::Vim.navigation(&Key::Char('s')), None);
+    }
+}
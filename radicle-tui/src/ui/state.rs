@@ -16,70 +16,170 @@ impl TabState {
             self.selected = 0;
         }
     }
+
+    pub fn decr_tab_index(&mut self, rewind: bool) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        } else if rewind {
+            self.selected = self.len.saturating_sub(1);
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct ItemState {
     selected: Option<usize>,
     len: usize,
+    /// Index of the first visible item.
+    offset: usize,
 }
 
 impl ItemState {
     pub fn new(selected: Option<usize>, len: usize) -> Self {
-        Self { selected, len }
+        Self {
+            selected,
+            len,
+            offset: 0,
+        }
     }
 
     pub fn selected(&self) -> Option<usize> {
         self.selected
     }
 
-    pub fn select_previous(&mut self) -> Option<usize> {
-        let old_index = self.selected();
-        let new_index = match old_index {
-            Some(selected) if selected == 0 => Some(0),
-            Some(selected) => Some(selected.saturating_sub(1)),
-            None => Some(0),
-        };
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
 
-        if old_index != new_index {
-            self.selected = new_index;
-            self.selected()
-        } else {
-            None
+    /// Scrolls as little as possible for the selection to be within the `height` items
+    /// shown from the offset on.
+    pub fn scroll_to_selection(&mut self, height: usize) {
+        self.offset = self.offset.min(self.len.saturating_sub(height.max(1)));
+
+        if let Some(selected) = self.selected {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if selected >= self.offset + height {
+                self.offset = selected + 1 - height.max(1);
+            }
         }
     }
 
-    pub fn select_next(&mut self) -> Option<usize> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Selects the item at the given index, if it's within bounds.
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.len);
+    }
+
+    /// Sets the item count and clamps the selection to the new bounds.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.selected = match self.selected {
+            _ if len == 0 => None,
+            Some(selected) => Some(selected.min(len - 1)),
+            None => Some(0),
+        };
+    }
+
+    /// Moves the selection by `delta` items, clamped to the bounds. Returns the new
+    /// selection if it changed.
+    pub fn select_by(&mut self, delta: isize) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
         let old_index = self.selected();
         let new_index = match old_index {
-            Some(selected) if selected >= self.len.saturating_sub(1) => {
-                Some(self.len.saturating_sub(1))
-            }
-            Some(selected) => Some(selected.saturating_add(1)),
-            None => Some(0),
+            Some(selected) => selected.saturating_add_signed(delta).min(self.len - 1),
+            None => 0,
         };
 
-        if old_index != new_index {
-            self.selected = new_index;
+        if old_index != Some(new_index) {
+            self.selected = Some(new_index);
             self.selected()
         } else {
             None
         }
     }
+
+    pub fn select_previous(&mut self) -> Option<usize> {
+        self.select_by(-1)
+    }
+
+    pub fn select_next(&mut self) -> Option<usize> {
+        self.select_by(1)
+    }
+
+    pub fn select_first(&mut self) -> Option<usize> {
+        self.select_by(isize::MIN)
+    }
+
+    pub fn select_last(&mut self) -> Option<usize> {
+        self.select_by(isize::MAX)
+    }
 }
 
+/// Nb. The selection is relative to the offset, since only the items from the offset on
+/// are expected to be rendered.
 impl From<&ItemState> for TableState {
     fn from(value: &ItemState) -> Self {
         let mut state = TableState::default();
-        state.select(value.selected);
+        state.select(value.selected.map(|s| s.saturating_sub(value.offset)));
         state
     }
 }
 
+/// Nb. The selection is relative to the offset, since only the items from the offset on
+/// are expected to be rendered.
 impl From<&ItemState> for ListState {
     fn from(value: &ItemState) -> Self {
         let mut state = ListState::default();
-        state.select(value.selected);
+        state.select(value.selected.map(|s| s.saturating_sub(value.offset)));
         state
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scroll_to_selection() {
+        let mut state = ItemState::new(Some(0), 20);
+
+        state.scroll_to_selection(5);
+        assert_eq!(state.offset(), 0);
+
+        // Moving down past the visible items scrolls by one.
+        state.select(Some(5));
+        state.scroll_to_selection(5);
+        assert_eq!(state.offset(), 1);
+
+        // Moving up within the visible items doesn't scroll.
+        state.select(Some(2));
+        state.scroll_to_selection(5);
+        assert_eq!(state.offset(), 1);
+
+        state.select(Some(0));
+        state.scroll_to_selection(5);
+        assert_eq!(state.offset(), 0);
+
+        state.select(Some(19));
+        state.scroll_to_selection(5);
+        assert_eq!(state.offset(), 15);
+
+        // Growing the viewport doesn't leave empty rows at the end.
+        state.scroll_to_selection(10);
+        assert_eq!(state.offset(), 10);
+
+        state.set_len(3);
+        state.scroll_to_selection(10);
+        assert_eq!(state.offset(), 0);
+    }
+}
@@ -1,5 +1,13 @@
+use std::env;
+use std::path::Path;
+use std::{fs, io};
+
 use tuirealm::props::Color;
 
+/// Environment variable that, if set to `1`, makes the TUI use ASCII icons only.
+/// Useful for terminals that don't have fonts with the required glyphs.
+pub const RAD_TUI_ASCII: &str = "RAD_TUI_ASCII";
+
 const COLOR_DEFAULT_FG: Color = Color::Rgb(200, 200, 200);
 const COLOR_DEFAULT_DARK_FG: Color = Color::Rgb(150, 150, 150);
 const COLOR_DEFAULT_DARK: Color = Color::Rgb(100, 100, 100);
@@ -7,6 +15,23 @@ const COLOR_DEFAULT_DARKER: Color = Color::Rgb(70, 70, 70);
 const COLOR_DEFAULT_DARKEST: Color = Color::Rgb(40, 40, 40);
 const COLOR_DEFAULT_FAINT: Color = Color::Rgb(20, 20, 20);
 
+/// An error loading a theme from a file.
+#[derive(thiserror::Error, Debug)]
+pub enum ThemeError {
+    /// The file couldn't be read.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The file isn't valid JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The theme has a key that isn't a known color or icon.
+    #[error("unknown theme key '{0}'")]
+    UnknownKey(String),
+    /// The value of a key has the wrong type or format.
+    #[error("invalid value for '{key}': {reason}")]
+    InvalidValue { key: String, reason: &'static str },
+}
+
 #[derive(Debug, Clone)]
 pub struct Colors {
     pub default_fg: Color,
@@ -35,6 +60,46 @@ pub struct Colors {
     pub context_id_fg: Color,
     pub context_id_bg: Color,
     pub context_id_author_fg: Color,
+    pub border_fg: Color,
+    pub scrollbar_fg: Color,
+}
+
+impl Colors {
+    /// Returns the color with the given name, as used in theme files.
+    fn get_mut(&mut self, key: &str) -> Option<&mut Color> {
+        let value = match key {
+            "default_fg" => &mut self.default_fg,
+            "tabs_highlighted_fg" => &mut self.tabs_highlighted_fg,
+            "workspaces_info_fg" => &mut self.workspaces_info_fg,
+            "labeled_container_bg" => &mut self.labeled_container_bg,
+            "item_list_highlighted_bg" => &mut self.item_list_highlighted_bg,
+            "property_name_fg" => &mut self.property_name_fg,
+            "property_divider_fg" => &mut self.property_divider_fg,
+            "shortcut_short_fg" => &mut self.shortcut_short_fg,
+            "shortcut_long_fg" => &mut self.shortcut_long_fg,
+            "shortcutbar_divider_fg" => &mut self.shortcutbar_divider_fg,
+            "browser_list_id" => &mut self.browser_list_id,
+            "browser_list_title" => &mut self.browser_list_title,
+            "browser_list_description" => &mut self.browser_list_description,
+            "browser_list_author" => &mut self.browser_list_author,
+            "browser_list_tags" => &mut self.browser_list_tags,
+            "browser_list_comments" => &mut self.browser_list_comments,
+            "browser_list_timestamp" => &mut self.browser_list_timestamp,
+            "browser_patch_list_head" => &mut self.browser_patch_list_head,
+            "browser_patch_list_added" => &mut self.browser_patch_list_added,
+            "browser_patch_list_removed" => &mut self.browser_patch_list_removed,
+            "context_bg" => &mut self.context_bg,
+            "context_light_bg" => &mut self.context_light_bg,
+            "context_badge_bg" => &mut self.context_badge_bg,
+            "context_id_fg" => &mut self.context_id_fg,
+            "context_id_bg" => &mut self.context_id_bg,
+            "context_id_author_fg" => &mut self.context_id_author_fg,
+            "border_fg" => &mut self.border_fg,
+            "scrollbar_fg" => &mut self.scrollbar_fg,
+            _ => return None,
+        };
+        Some(value)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +109,68 @@ pub struct Icons {
     pub tab_divider: char,
     pub tab_overline: char,
     pub whitespace: char,
+    pub state: char,
+    pub state_merged: char,
+    pub arrow_up: char,
+    pub arrow_down: char,
+    pub marker: char,
+    pub scrollbar_thumb: char,
+}
+
+impl Default for Icons {
+    fn default() -> Self {
+        Self {
+            property_divider: '∙',
+            shortcutbar_divider: '∙',
+            tab_divider: '|',
+            tab_overline: '▔',
+            whitespace: ' ',
+            state: '●',
+            state_merged: '✔',
+            arrow_up: '↑',
+            arrow_down: '↓',
+            marker: '▌',
+            scrollbar_thumb: '┃',
+        }
+    }
+}
+
+impl Icons {
+    /// Returns the icon with the given name, as used in theme files.
+    fn get_mut(&mut self, key: &str) -> Option<&mut char> {
+        let value = match key {
+            "property_divider" => &mut self.property_divider,
+            "shortcutbar_divider" => &mut self.shortcutbar_divider,
+            "tab_divider" => &mut self.tab_divider,
+            "tab_overline" => &mut self.tab_overline,
+            "whitespace" => &mut self.whitespace,
+            "state" => &mut self.state,
+            "state_merged" => &mut self.state_merged,
+            "arrow_up" => &mut self.arrow_up,
+            "arrow_down" => &mut self.arrow_down,
+            "marker" => &mut self.marker,
+            "scrollbar_thumb" => &mut self.scrollbar_thumb,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Icons that only use ASCII characters.
+    pub fn ascii() -> Self {
+        Self {
+            property_divider: '-',
+            shortcutbar_divider: '-',
+            tab_divider: '|',
+            tab_overline: '-',
+            whitespace: ' ',
+            state: '*',
+            state_merged: '+',
+            arrow_up: '^',
+            arrow_down: 'v',
+            marker: '>',
+            scrollbar_thumb: '#',
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -51,18 +178,18 @@ pub struct Tables {
     pub spacing: u16,
 }
 
-/// The Radicle TUI theme. Will be defined in a JSON config file in the
-/// future. e.g.:
+/// The Radicle TUI theme. Can be loaded from a JSON file with
+/// [`Theme::from_file`], e.g.:
 /// {
-///     "name": "Default",
+///     "name": "Custom",
 ///     "colors": {
-///         "foreground": "#ffffff",
-///         "propertyForeground": "#ffffff",
-///         "highlightedBackground": "#000000",
+///         "default_fg": "#ffffff",
+///         "border_fg": "gray",
+///         "item_list_highlighted_bg": "#303030"
 ///     },
 ///     "icons": {
-///         "workspaces.divider": "|",
-///         "shortcuts.divider: "∙",
+///         "tab_divider": "|",
+///         "shortcutbar_divider": "∙"
 ///     }
 /// }
 #[derive(Debug, Clone)]
@@ -73,6 +200,116 @@ pub struct Theme {
     pub tables: Tables,
 }
 
+impl Theme {
+    /// Loads a theme from a JSON file. Colors and icons that aren't in the file are
+    /// taken from the default theme.
+    pub fn from_file(path: &Path) -> Result<Theme, ThemeError> {
+        let json = fs::read_to_string(path)?;
+
+        Self::from_json(&json)
+    }
+
+    fn from_json(json: &str) -> Result<Theme, ThemeError> {
+        let root: serde_json::Value = serde_json::from_str(json)?;
+        let mut theme = default_dark();
+
+        for (key, value) in object(&root, "theme")? {
+            match key.as_str() {
+                "name" => {
+                    theme.name = value
+                        .as_str()
+                        .ok_or_else(|| invalid("name", "expected a string"))?
+                        .to_owned();
+                }
+                "colors" => {
+                    for (name, value) in object(value, "colors")? {
+                        let key = format!("colors.{name}");
+                        let color = theme
+                            .colors
+                            .get_mut(name)
+                            .ok_or_else(|| ThemeError::UnknownKey(key.clone()))?;
+
+                        *color = value.as_str().and_then(parse_color).ok_or_else(|| {
+                            invalid(&key, "expected a hex color or color name, eg. `#ff00ff`")
+                        })?;
+                    }
+                }
+                "icons" => {
+                    for (name, value) in object(value, "icons")? {
+                        let key = format!("icons.{name}");
+                        let icon = theme
+                            .icons
+                            .get_mut(name)
+                            .ok_or_else(|| ThemeError::UnknownKey(key.clone()))?;
+                        let mut chars = value.as_str().unwrap_or_default().chars();
+
+                        *icon = match (chars.next(), chars.next()) {
+                            (Some(c), None) => c,
+                            _ => return Err(invalid(&key, "expected a single character")),
+                        };
+                    }
+                }
+                _ => return Err(ThemeError::UnknownKey(key.to_owned())),
+            }
+        }
+        Ok(theme)
+    }
+}
+
+fn object<'a>(
+    value: &'a serde_json::Value,
+    key: &str,
+) -> Result<&'a serde_json::Map<String, serde_json::Value>, ThemeError> {
+    value
+        .as_object()
+        .ok_or_else(|| invalid(key, "expected an object"))
+}
+
+fn invalid(key: &str, reason: &'static str) -> ThemeError {
+    ThemeError::InvalidValue {
+        key: key.to_owned(),
+        reason,
+    }
+}
+
+/// Parses a hex color of the form `#rrggbb`, or the name of an ANSI color, eg. `lightblue`.
+fn parse_color(s: &str) -> Option<Color> {
+    let Some(hex) = s.strip_prefix('#') else {
+        return named_color(s);
+    };
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Parses the name of one of the 16 ANSI colors, or `reset` for the terminal default.
+fn named_color(s: &str) -> Option<Color> {
+    let color = match s.to_lowercase().replace(['_', '-'], "").as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
 pub fn default_dark() -> Theme {
     Theme {
         name: String::from("Default"),
@@ -103,14 +340,98 @@ pub fn default_dark() -> Theme {
             context_id_fg: Color::Cyan,
             context_id_bg: COLOR_DEFAULT_DARKEST,
             context_id_author_fg: Color::Gray,
+            border_fg: Color::Rgb(48, 48, 48),
+            scrollbar_fg: Color::Rgb(88, 88, 88),
         },
-        icons: Icons {
-            property_divider: '∙',
-            shortcutbar_divider: '∙',
-            tab_divider: '|',
-            tab_overline: '▔',
-            whitespace: ' ',
-        },
+        icons: Icons::default(),
         tables: Tables { spacing: 2 },
     }
 }
+
+/// Returns the default theme, with ASCII icons if [`RAD_TUI_ASCII`] is set.
+pub fn from_env() -> Theme {
+    let mut theme = default_dark();
+
+    if env::var(RAD_TUI_ASCII).map_or(false, |v| v == "1") {
+        theme.icons = Icons::ascii();
+    }
+    theme
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ascii_icons() {
+        let Icons {
+            property_divider,
+            shortcutbar_divider,
+            tab_divider,
+            tab_overline,
+            whitespace,
+            state,
+            state_merged,
+            arrow_up,
+            arrow_down,
+            marker,
+            scrollbar_thumb,
+        } = Icons::ascii();
+
+        for icon in [
+            property_divider,
+            shortcutbar_divider,
+            tab_divider,
+            tab_overline,
+            whitespace,
+            state,
+            state_merged,
+            arrow_up,
+            arrow_down,
+            marker,
+            scrollbar_thumb,
+        ] {
+            assert!(icon.is_ascii(), "icon {icon:?} is not ASCII");
+        }
+    }
+
+    #[test]
+    fn test_theme_from_json() {
+        let theme = Theme::from_json(
+            r##"{
+                "name": "Custom",
+                "colors": { "browser_list_id": "#ff0080", "border_fg": "light-blue" },
+                "icons": { "tab_divider": "/" }
+            }"##,
+        )
+        .unwrap();
+        let default = default_dark();
+
+        assert_eq!(theme.name, "Custom");
+        assert_eq!(theme.colors.browser_list_id, Color::Rgb(255, 0, 128));
+        assert_eq!(theme.colors.border_fg, Color::LightBlue);
+        assert_eq!(theme.colors.default_fg, default.colors.default_fg);
+        assert_eq!(theme.icons.tab_divider, '/');
+        assert_eq!(theme.icons.state, default.icons.state);
+    }
+
+    #[test]
+    fn test_theme_from_json_errors() {
+        assert!(matches!(
+            Theme::from_json(r##"{ "colors": { "browser_list_id": "#ff00" } }"##),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "colors.browser_list_id"
+        ));
+        assert!(matches!(
+            Theme::from_json(r##"{ "colors": { "unknown": "#ff0000" } }"##),
+            Err(ThemeError::UnknownKey(key)) if key == "colors.unknown"
+        ));
+        assert!(matches!(
+            Theme::from_json(r##"{ "colors": { "border_fg": "purple" } }"##),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "colors.border_fg"
+        ));
+        assert!(matches!(
+            Theme::from_json(r##"{ "icons": { "state": "**" } }"##),
+            Err(ThemeError::InvalidValue { key, .. }) if key == "icons.state"
+        ));
+    }
+}
@@ -4,7 +4,7 @@ pub mod issue;
 pub mod patch;
 mod utils;
 
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 
 use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::props::{AttrValue, Attribute, Color, Props};
@@ -35,6 +35,12 @@ impl<T: WidgetComponent> Deref for Widget<T> {
     }
 }
 
+impl<T: WidgetComponent> DerefMut for Widget<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.component
+    }
+}
+
 impl<T: WidgetComponent> Widget<T> {
     pub fn new(component: T) -> Self {
         Widget {
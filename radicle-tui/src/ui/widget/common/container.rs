@@ -1,7 +1,5 @@
 use tuirealm::command::{Cmd, CmdResult};
-use tuirealm::props::{
-    AttrValue, Attribute, BorderSides, BorderType, Color, Props, Style, TextModifiers,
-};
+use tuirealm::props::{AttrValue, Attribute, BorderSides, BorderType, Props, Style, TextModifiers};
 use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
 use tuirealm::tui::widgets::{Block, Cell, Row};
 use tuirealm::{Frame, MockComponent, State, StateValue};
@@ -112,17 +110,17 @@ impl WidgetComponent for Tabs {
     fn perform(&mut self, _properties: &Props, cmd: Cmd) -> CmdResult {
         use tuirealm::command::Direction;
 
+        let prev = self.state.selected;
         match cmd {
-            Cmd::Move(Direction::Right) => {
-                let prev = self.state.selected;
-                self.state.incr_tab_index(true);
-                if prev != self.state.selected {
-                    CmdResult::Changed(self.state())
-                } else {
-                    CmdResult::None
-                }
-            }
-            _ => CmdResult::None,
+            Cmd::Move(Direction::Right) => self.state.incr_tab_index(true),
+            Cmd::Move(Direction::Left) => self.state.decr_tab_index(true),
+            _ => return CmdResult::None,
+        }
+
+        if prev != self.state.selected {
+            CmdResult::Changed(self.state())
+        } else {
+            CmdResult::None
         }
     }
 }
@@ -152,7 +150,7 @@ impl<const W: usize> WidgetComponent for Header<W> {
         if display {
             let block = HeaderBlock::default()
                 .borders(BorderSides::all())
-                .border_style(Style::default().fg(Color::Rgb(48, 48, 48)))
+                .border_style(Style::default().fg(self.theme.colors.border_fg))
                 .border_type(BorderType::Rounded);
             frame.render_widget(block, area);
 
@@ -163,7 +161,7 @@ impl<const W: usize> WidgetComponent for Header<W> {
                 .horizontal_margin(1)
                 .split(area);
 
-            let widths = utils::column_widths(area, &self.widths, self.theme.tables.spacing);
+            let widths = utils::column_widths(layout[0], &self.widths, self.theme.tables.spacing);
             let header: [Cell; W] = self
                 .header
                 .iter()
@@ -195,11 +193,12 @@ impl<const W: usize> WidgetComponent for Header<W> {
 
 pub struct Container {
     component: Box<dyn MockComponent>,
+    theme: Theme,
 }
 
 impl Container {
-    pub fn new(component: Box<dyn MockComponent>) -> Self {
-        Self { component }
+    pub fn new(component: Box<dyn MockComponent>, theme: Theme) -> Self {
+        Self { component, theme }
     }
 }
 
@@ -222,7 +221,7 @@ impl WidgetComponent for Container {
 
             let block = Block::default()
                 .borders(BorderSides::ALL)
-                .border_style(Style::default().fg(Color::Rgb(48, 48, 48)))
+                .border_style(Style::default().fg(self.theme.colors.border_fg))
                 .border_type(BorderType::Rounded);
             frame.render_widget(block, area);
         }
@@ -240,11 +239,16 @@ impl WidgetComponent for Container {
 pub struct LabeledContainer {
     header: Widget<Header<1>>,
     component: Box<dyn MockComponent>,
+    theme: Theme,
 }
 
 impl LabeledContainer {
-    pub fn new(header: Widget<Header<1>>, component: Box<dyn MockComponent>) -> Self {
-        Self { header, component }
+    pub fn new(header: Widget<Header<1>>, component: Box<dyn MockComponent>, theme: Theme) -> Self {
+        Self {
+            header,
+            component,
+            theme,
+        }
     }
 }
 
@@ -276,7 +280,7 @@ impl WidgetComponent for LabeledContainer {
 
             let block = Block::default()
                 .borders(BorderSides::BOTTOM | BorderSides::LEFT | BorderSides::RIGHT)
-                .border_style(Style::default().fg(Color::Rgb(48, 48, 48)))
+                .border_style(Style::default().fg(self.theme.colors.border_fg))
                 .border_type(BorderType::Rounded);
             frame.render_widget(block, layout[1]);
 
@@ -292,3 +296,41 @@ impl WidgetComponent for LabeledContainer {
         self.component.perform(cmd)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use tuirealm::command::Direction;
+
+    use super::*;
+    use crate::ui::widget::common::label;
+
+    fn tabs(len: usize) -> Widget<Tabs> {
+        let tabs = (0..len).map(|i| label(&i.to_string())).collect();
+        Widget::new(Tabs::new(tabs, label("-")))
+    }
+
+    #[test]
+    fn test_tabs_move() {
+        let mut tabs = tabs(3);
+        assert_eq!(tabs.state(), State::One(StateValue::U16(0)));
+
+        assert_eq!(
+            tabs.perform(Cmd::Move(Direction::Right)),
+            CmdResult::Changed(State::One(StateValue::U16(1)))
+        );
+        assert_eq!(
+            tabs.perform(Cmd::Move(Direction::Left)),
+            CmdResult::Changed(State::One(StateValue::U16(0)))
+        );
+        // Moving past either end wraps around.
+        assert_eq!(
+            tabs.perform(Cmd::Move(Direction::Left)),
+            CmdResult::Changed(State::One(StateValue::U16(2)))
+        );
+        assert_eq!(
+            tabs.perform(Cmd::Move(Direction::Right)),
+            CmdResult::Changed(State::One(StateValue::U16(0)))
+        );
+        assert_eq!(tabs.perform(Cmd::Move(Direction::Up)), CmdResult::None);
+    }
+}
@@ -1,7 +1,10 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
 use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::props::{AttrValue, Attribute, BorderSides, BorderType, Color, Props, Style};
 use tuirealm::tui::layout::{Constraint, Direction, Layout, Rect};
-use tuirealm::tui::widgets::{Block, Cell, ListState, Row, TableState};
+use tuirealm::tui::widgets::{Block, Cell, ListState, Paragraph, Row, TableState};
 use tuirealm::{Frame, MockComponent, State, StateValue};
 
 use crate::ui::layout;
@@ -17,6 +20,15 @@ use super::*;
 pub trait TableItem<const W: usize> {
     /// Should return fields as table cells.
     fn row(&self, theme: &Theme) -> [Cell; W];
+
+    /// Should compare the field in the given column to the one of another item.
+    /// Columns that always compare as equal, the default, can't be sorted by.
+    fn compare(&self, _other: &Self, _column: usize) -> Ordering {
+        Ordering::Equal
+    }
+
+    /// Should return the text that is searched when filtering rows.
+    fn filter_text(&self) -> String;
 }
 
 /// A generic item that can be displayed in a list.
@@ -38,6 +50,84 @@ pub enum ColumnWidth {
     Grow,
 }
 
+/// Sort order of a table column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
+/// Custom command that sorts a [`Table`] by its next column.
+pub const CMD_SORT_COLUMN: &str = "sort-column";
+/// Custom command that reverses the sort order of a [`Table`].
+pub const CMD_SORT_ORDER: &str = "sort-order";
+/// Custom command that starts filtering the rows of a [`Table`].
+pub const CMD_FILTER: &str = "filter";
+/// Custom command that stops editing the filter of a [`Table`], keeping it applied.
+pub const CMD_FILTER_APPLY: &str = "filter-apply";
+
+/// Custom command that marks or unmarks the selected item of a [`Table`] or [`List`].
+pub const CMD_TOGGLE_MARK: &str = "toggle-mark";
+
+/// Custom attribute that is set on components while their filter is being edited.
+pub const ATTR_FILTERING: &str = "filtering";
+
+/// Command result of a selection change, if any.
+fn selection_changed(selected: Option<usize>) -> CmdResult {
+    match selected {
+        Some(selected) => CmdResult::Changed(State::One(StateValue::Usize(selected))),
+        None => CmdResult::None,
+    }
+}
+
+/// Draws the theme's marker icon into the one column wide `gutter`, next to every
+/// visible row that is marked.
+fn render_marks(
+    frame: &mut Frame,
+    gutter: Rect,
+    marked: impl Iterator<Item = bool>,
+    offset: usize,
+    theme: &Theme,
+) {
+    let height = gutter.height as usize;
+    let marker = Paragraph::new(theme.icons.marker.to_string())
+        .style(Style::default().fg(theme.colors.tabs_highlighted_fg));
+
+    for (row, marked) in marked.skip(offset).take(height).enumerate() {
+        if marked {
+            let area = Rect::new(gutter.x, gutter.y + row as u16, 1, 1);
+            frame.render_widget(marker.clone(), area);
+        }
+    }
+}
+
+/// Position and length of a scrollbar thumb in a track of `height` rows, given the index
+/// of the first visible row and the total row count. Returns `None` if all rows fit.
+fn scrollbar_thumb(offset: usize, len: usize, height: usize) -> Option<(usize, usize)> {
+    if height == 0 || len <= height {
+        return None;
+    }
+    let thumb = (height * height / len).max(1);
+    let start = offset.min(len - height) * (height - thumb) / (len - height);
+
+    Some((start, thumb))
+}
+
+/// Draws a scrollbar thumb into the one column wide `track`.
+fn render_scrollbar(frame: &mut Frame, track: Rect, len: usize, offset: usize, theme: &Theme) {
+    let height = track.height as usize;
+
+    if let Some((start, thumb)) = scrollbar_thumb(offset, len, height) {
+        let lines = vec![theme.icons.scrollbar_thumb.to_string(); thumb].join("\n");
+        let area = Rect::new(track.x, track.y + start as u16, 1, thumb as u16);
+
+        frame.render_widget(
+            Paragraph::new(lines).style(Style::default().fg(theme.colors.scrollbar_fg)),
+            area,
+        );
+    }
+}
+
 /// A component that displays a labeled property.
 #[derive(Clone)]
 pub struct Property {
@@ -186,12 +276,24 @@ where
 {
     /// Items hold by this model.
     items: Vec<V>,
+    /// Indices of the items that match the filter, in display order.
+    visible: Vec<usize>,
     /// The table header.
     header: [Widget<Label>; W],
     /// Grow behavior of table columns.
     widths: [ColumnWidth; W],
     /// State that keeps track of the selection.
     state: ItemState,
+    /// Indices of marked items in `items`, independent of the selection.
+    marked: HashSet<usize>,
+    /// The column and order items are sorted by, if any.
+    sort: Option<(usize, Order)>,
+    /// Case-insensitive query that rows are filtered by.
+    filter: String,
+    /// True while the filter is being edited.
+    filtering: bool,
+    /// Number of visible rows, as of the last render.
+    page_size: usize,
     /// The current theme.
     theme: Theme,
 }
@@ -208,12 +310,171 @@ where
     ) -> Self {
         Self {
             items: items.to_vec(),
+            visible: (0..items.len()).collect(),
             header,
             widths,
             state: ItemState::new(Some(0), items.len()),
+            marked: HashSet::new(),
+            sort: None,
+            filter: String::new(),
+            filtering: false,
+            page_size: 1,
             theme,
         }
     }
+
+    /// Replaces all items, keeping the selection within the new bounds.
+    /// Marks are cleared.
+    pub fn set_items(&mut self, items: Vec<V>) {
+        self.items = items;
+        self.marked.clear();
+        self.update(None);
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.set_items(vec![]);
+    }
+
+    pub fn items(&self) -> &Vec<V> {
+        &self.items
+    }
+
+    pub fn sort(&self) -> Option<(usize, Order)> {
+        self.sort
+    }
+
+    /// Returns the selected item, if any.
+    pub fn selection(&self) -> Option<&V> {
+        self.selected_index().map(|index| &self.items[index])
+    }
+
+    /// Returns the marked items, in display order. Items hidden by the filter
+    /// are included.
+    pub fn selections(&self) -> Vec<&V> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.marked.contains(index))
+            .map(|(_, item)| item)
+            .collect()
+    }
+
+    /// Marks the selected item if it isn't marked, and unmarks it otherwise.
+    pub fn toggle_mark(&mut self) -> CmdResult {
+        match self.selected_index() {
+            Some(index) => {
+                if !self.marked.remove(&index) {
+                    self.marked.insert(index);
+                }
+                CmdResult::Changed(self.state())
+            }
+            None => CmdResult::None,
+        }
+    }
+
+    /// Sorts items by the given column. The selection follows the selected item.
+    pub fn sort_by(&mut self, column: usize, order: Order) {
+        self.sort = Some((column, order));
+        self.update(self.selected_index());
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        self.filtering
+    }
+
+    /// Only shows rows whose filter text contains the given query, ignoring case.
+    /// The selection follows the selected item, if it's still visible.
+    pub fn filter_by(&mut self, query: &str) {
+        self.filter = query.to_owned();
+        self.update(self.selected_index());
+    }
+
+    /// Index of the selected item in `items`.
+    fn selected_index(&self) -> Option<usize> {
+        self.state
+            .selected()
+            .and_then(|selected| self.visible.get(selected).copied())
+    }
+
+    /// Sorts and filters items. If the item at the given index is visible afterwards,
+    /// it's selected, otherwise the selection is kept within the new bounds.
+    fn update(&mut self, selected: Option<usize>) {
+        let mut selected = selected;
+
+        if let Some((column, order)) = self.sort {
+            let mut items = self.items.drain(..).enumerate().collect::<Vec<_>>();
+
+            items.sort_by(|(_, a), (_, b)| match order {
+                Order::Ascending => a.compare(b, column),
+                Order::Descending => b.compare(a, column),
+            });
+            selected = selected.and_then(|s| items.iter().position(|(index, _)| *index == s));
+            self.marked = items
+                .iter()
+                .enumerate()
+                .filter(|(_, (index, _))| self.marked.contains(index))
+                .map(|(position, _)| position)
+                .collect();
+            self.items = items.into_iter().map(|(_, item)| item).collect();
+        }
+
+        let query = self.filter.to_lowercase();
+        self.visible = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.filter_text().to_lowercase().contains(&query))
+            .map(|(index, _)| index)
+            .collect();
+        self.state.set_len(self.visible.len());
+
+        if let Some(selected) = selected.and_then(|s| self.visible.iter().position(|i| *i == s)) {
+            self.state.select(Some(selected));
+        }
+    }
+
+    fn header(&self) -> [Widget<Label>; W] {
+        let mut header = self.header.clone();
+
+        if let Some((column, order)) = self.sort {
+            let arrow = match order {
+                Order::Ascending => self.theme.icons.arrow_up,
+                Order::Descending => self.theme.icons.arrow_down,
+            };
+            if let Some(label) = header.get_mut(column) {
+                let content = label
+                    .query(Attribute::Content)
+                    .unwrap_or(AttrValue::String(String::default()))
+                    .unwrap_string();
+                label.attr(
+                    Attribute::Content,
+                    AttrValue::String(format!("{content}{arrow}")),
+                );
+            }
+        }
+
+        // Show the filter query next to the first growing column's title.
+        if self.filtering || !self.filter.is_empty() {
+            let column = self.widths.iter().position(|w| *w == ColumnWidth::Grow);
+            if let Some(label) = column.and_then(|column| header.get_mut(column)) {
+                let content = label
+                    .query(Attribute::Content)
+                    .unwrap_or(AttrValue::String(String::default()))
+                    .unwrap_string();
+                let cursor = if self.filtering { "_" } else { "" };
+                label.attr(
+                    Attribute::Content,
+                    AttrValue::String(format!("{content} /{}{cursor}", self.filter)),
+                );
+            }
+        }
+        header
+    }
 }
 
 impl<V, const W: usize> WidgetComponent for Table<V, W>
@@ -230,52 +491,121 @@ where
             .constraints(vec![Constraint::Length(3), Constraint::Min(1)])
             .split(area);
 
-        let widths = utils::column_widths(area, &self.widths, self.theme.tables.spacing);
+        let block = Block::default()
+            .borders(BorderSides::BOTTOM | BorderSides::LEFT | BorderSides::RIGHT)
+            .border_style(Style::default().fg(self.theme.colors.border_fg))
+            .border_type(BorderType::Rounded);
+        // Nb. Widths need to be calculated from the inner area, the same way the
+        // header does, in order for columns to line up.
+        let inner = block.inner(layout[1]);
+        let widths = utils::column_widths(inner, &self.widths, self.theme.tables.spacing);
+
+        self.page_size = (inner.height as usize).max(1);
+        self.state.scroll_to_selection(self.page_size);
+
+        // Only the rows from the offset on are rendered, so that the viewport follows the
+        // selection without jumping.
+        let offset = self.state.offset();
         let rows: Vec<Row<'_>> = self
-            .items
+            .visible
             .iter()
-            .map(|item| Row::new(item.row(&self.theme)))
+            .skip(offset)
+            .take(self.page_size)
+            .map(|index| Row::new(self.items[*index].row(&self.theme)))
             .collect();
 
         let table = tuirealm::tui::widgets::Table::new(rows)
-            .block(
-                Block::default()
-                    .borders(BorderSides::BOTTOM | BorderSides::LEFT | BorderSides::RIGHT)
-                    .border_style(Style::default().fg(Color::Rgb(48, 48, 48)))
-                    .border_type(BorderType::Rounded),
-            )
+            .block(block)
             .highlight_style(Style::default().bg(highlight))
             .column_spacing(self.theme.tables.spacing)
             .widths(&widths);
 
-        let mut header = Widget::new(Header::new(
-            self.header.clone(),
-            self.widths,
-            self.theme.clone(),
-        ));
+        let mut header = Widget::new(Header::new(self.header(), self.widths, self.theme.clone()));
         header.view(frame, layout[0]);
         frame.render_stateful_widget(table, layout[1], &mut TableState::from(&self.state));
+
+        // Marks are drawn onto the left border, so that columns stay aligned with the header.
+        let gutter = Rect::new(layout[1].x, inner.y, 1, inner.height);
+        let marked = self.visible.iter().map(|index| self.marked.contains(index));
+        render_marks(frame, gutter, marked, offset, &self.theme);
+
+        let track = Rect::new(inner.x + inner.width, inner.y, 1, inner.height);
+        render_scrollbar(frame, track, self.visible.len(), offset, &self.theme);
     }
 
     fn state(&self) -> State {
-        State::None
+        match self.state.selected() {
+            Some(selected) => State::One(StateValue::Usize(selected)),
+            None => State::None,
+        }
     }
 
     fn perform(&mut self, _properties: &Props, cmd: Cmd) -> CmdResult {
-        use tuirealm::command::Direction;
+        use tuirealm::command::{Direction, Position};
         match cmd {
-            Cmd::Move(Direction::Up) => match self.state.select_previous() {
-                Some(selected) => CmdResult::Changed(State::One(StateValue::Usize(selected))),
-                None => CmdResult::None,
-            },
-            Cmd::Move(Direction::Down) => match self.state.select_next() {
-                Some(selected) => CmdResult::Changed(State::One(StateValue::Usize(selected))),
-                None => CmdResult::None,
-            },
+            Cmd::Move(Direction::Up) => selection_changed(self.state.select_previous()),
+            Cmd::Move(Direction::Down) => selection_changed(self.state.select_next()),
+            Cmd::Scroll(Direction::Up) => {
+                selection_changed(self.state.select_by(-(self.page_size as isize)))
+            }
+            Cmd::Scroll(Direction::Down) => {
+                selection_changed(self.state.select_by(self.page_size as isize))
+            }
+            Cmd::GoTo(Position::Begin) => selection_changed(self.state.select_first()),
+            Cmd::GoTo(Position::End) => selection_changed(self.state.select_last()),
             Cmd::Submit => match self.state.selected() {
                 Some(selected) => CmdResult::Submit(State::One(StateValue::Usize(selected))),
                 None => CmdResult::None,
             },
+            Cmd::Custom(CMD_SORT_COLUMN) => {
+                let (column, order) = match self.sort {
+                    Some((column, order)) => ((column + 1) % W, order),
+                    None => (0, Order::Ascending),
+                };
+                self.sort_by(column, order);
+
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom(CMD_SORT_ORDER) => match self.sort {
+                Some((column, order)) => {
+                    let order = match order {
+                        Order::Ascending => Order::Descending,
+                        Order::Descending => Order::Ascending,
+                    };
+                    self.sort_by(column, order);
+
+                    CmdResult::Changed(self.state())
+                }
+                None => CmdResult::None,
+            },
+            Cmd::Custom(CMD_TOGGLE_MARK) => self.toggle_mark(),
+            Cmd::Custom(CMD_FILTER) => {
+                self.filtering = true;
+                CmdResult::None
+            }
+            Cmd::Custom(CMD_FILTER_APPLY) => {
+                self.filtering = false;
+                CmdResult::None
+            }
+            Cmd::Type(c) if self.filtering => {
+                let query = format!("{}{c}", self.filter);
+                self.filter_by(&query);
+
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Delete if self.filtering => {
+                let mut query = self.filter.clone();
+                query.pop();
+                self.filter_by(&query);
+
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Cancel if self.filtering || !self.filter.is_empty() => {
+                self.filtering = false;
+                self.filter_by("");
+
+                CmdResult::Changed(self.state())
+            }
             _ => CmdResult::None,
         }
     }
@@ -290,6 +620,10 @@ where
     items: Vec<V>,
     /// State keeps track of the current selection.
     state: ItemState,
+    /// Indices of marked items, independent of the selection.
+    marked: HashSet<usize>,
+    /// Number of visible items, as of the last render.
+    page_size: usize,
     /// The current theme.
     theme: Theme,
 }
@@ -307,9 +641,64 @@ where
         Self {
             items: items.to_vec(),
             state: ItemState::new(selected, items.len()),
+            marked: HashSet::new(),
+            page_size: 1,
             theme,
         }
     }
+
+    /// Replaces all items, keeping the selection within the new bounds.
+    /// Marks are cleared.
+    pub fn set_items(&mut self, items: Vec<V>) {
+        self.state.set_len(items.len());
+        self.marked.clear();
+        self.items = items;
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.set_items(vec![]);
+    }
+
+    pub fn items(&self) -> &Vec<V> {
+        &self.items
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    /// Selects the item at the given index, if it's within bounds.
+    pub fn select(&mut self, index: usize) -> CmdResult {
+        if self.state.selected() == Some(index) || index >= self.items.len() {
+            return CmdResult::None;
+        }
+        self.state.select(Some(index));
+        selection_changed(self.state.selected())
+    }
+
+    /// Returns the marked items, in order.
+    pub fn selections(&self) -> Vec<&V> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.marked.contains(index))
+            .map(|(_, item)| item)
+            .collect()
+    }
+
+    /// Marks the selected item if it isn't marked, and unmarks it otherwise.
+    pub fn toggle_mark(&mut self) -> CmdResult {
+        match self.state.selected() {
+            Some(index) => {
+                if !self.marked.remove(&index) {
+                    self.marked.insert(index);
+                }
+                CmdResult::Changed(self.state())
+            }
+            None => CmdResult::None,
+        }
+    }
 }
 
 impl<V> WidgetComponent for List<V>
@@ -328,36 +717,381 @@ where
             .constraints(vec![Constraint::Min(1), Constraint::Length(1)])
             .split(area);
 
+        self.page_size = (layout[0].height as usize).max(1);
+        self.state.scroll_to_selection(self.page_size);
+
+        // Only the items from the offset on are rendered, so that the viewport follows the
+        // selection without jumping.
+        let offset = self.state.offset();
         let rows: Vec<ListItem> = self
             .items
             .iter()
+            .skip(offset)
+            .take(self.page_size)
             .map(|item| item.row(&self.theme))
             .collect();
         let list = List::new(rows).highlight_style(Style::default().bg(highlight));
 
-        frame.render_stateful_widget(list, layout[0], &mut ListState::from(&self.state));
+        // A gutter for marks is only reserved while there are any.
+        let area = if self.marked.is_empty() {
+            layout[0]
+        } else {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Length(2), Constraint::Min(1)])
+                .split(layout[0]);
+            let marked = (0..self.items.len()).map(|index| self.marked.contains(&index));
+            render_marks(frame, columns[0], marked, offset, &self.theme);
+
+            columns[1]
+        };
+        frame.render_stateful_widget(list, area, &mut ListState::from(&self.state));
     }
 
     fn state(&self) -> State {
-        State::None
+        match self.state.selected() {
+            Some(selected) => State::One(StateValue::Usize(selected)),
+            None => State::None,
+        }
     }
 
     fn perform(&mut self, _properties: &Props, cmd: Cmd) -> CmdResult {
-        use tuirealm::command::Direction;
+        use tuirealm::command::{Direction, Position};
         match cmd {
-            Cmd::Move(Direction::Up) => match self.state.select_previous() {
-                Some(selected) => CmdResult::Changed(State::One(StateValue::Usize(selected))),
-                None => CmdResult::None,
-            },
-            Cmd::Move(Direction::Down) => match self.state.select_next() {
-                Some(selected) => CmdResult::Changed(State::One(StateValue::Usize(selected))),
-                None => CmdResult::None,
-            },
+            Cmd::Move(Direction::Up) => selection_changed(self.state.select_previous()),
+            Cmd::Move(Direction::Down) => selection_changed(self.state.select_next()),
+            Cmd::Scroll(Direction::Up) => {
+                selection_changed(self.state.select_by(-(self.page_size as isize)))
+            }
+            Cmd::Scroll(Direction::Down) => {
+                selection_changed(self.state.select_by(self.page_size as isize))
+            }
+            Cmd::GoTo(Position::Begin) => selection_changed(self.state.select_first()),
+            Cmd::GoTo(Position::End) => selection_changed(self.state.select_last()),
             Cmd::Submit => match self.state.selected() {
                 Some(selected) => CmdResult::Submit(State::One(StateValue::Usize(selected))),
                 None => CmdResult::None,
             },
+            Cmd::Custom(CMD_TOGGLE_MARK) => self.toggle_mark(),
             _ => CmdResult::None,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use tuirealm::command::{Direction as MoveDirection, Position};
+
+    use super::*;
+    use crate::ui::theme;
+
+    #[derive(Clone, PartialEq)]
+    struct Item(usize);
+
+    impl TableItem<1> for Item {
+        fn row(&self, _theme: &Theme) -> [Cell; 1] {
+            [Cell::from(self.0.to_string())]
+        }
+
+        fn compare(&self, other: &Self, _column: usize) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+
+        fn filter_text(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    impl ListItem for Item {
+        fn row(&self, _theme: &Theme) -> tuirealm::tui::widgets::ListItem {
+            tuirealm::tui::widgets::ListItem::new(self.0.to_string())
+        }
+    }
+
+    fn table(len: usize) -> Widget<Table<Item, 1>> {
+        let items = (0..len).map(Item).collect::<Vec<_>>();
+        let table = Table::new(
+            &items,
+            [label("#")],
+            [ColumnWidth::Grow],
+            theme::default_dark(),
+        );
+        Widget::new(table)
+    }
+
+    #[test]
+    fn test_scrollbar_thumb() {
+        // Everything fits.
+        assert_eq!(scrollbar_thumb(0, 5, 10), None);
+        assert_eq!(scrollbar_thumb(0, 10, 10), None);
+
+        // Half of the rows are visible.
+        assert_eq!(scrollbar_thumb(0, 20, 10), Some((0, 5)));
+        assert_eq!(scrollbar_thumb(5, 20, 10), Some((2, 5)));
+        assert_eq!(scrollbar_thumb(10, 20, 10), Some((5, 5)));
+
+        // The thumb is never shorter than one row.
+        assert_eq!(scrollbar_thumb(0, 1000, 10), Some((0, 1)));
+        assert_eq!(scrollbar_thumb(990, 1000, 10), Some((9, 1)));
+    }
+
+    #[test]
+    fn test_table_selection_changed() {
+        let mut table = table(3);
+        assert_eq!(table.state(), State::One(StateValue::Usize(0)));
+
+        assert_eq!(
+            table.perform(Cmd::Move(MoveDirection::Down)),
+            CmdResult::Changed(State::One(StateValue::Usize(1)))
+        );
+        assert_eq!(
+            table.perform(Cmd::Move(MoveDirection::Down)),
+            CmdResult::Changed(State::One(StateValue::Usize(2)))
+        );
+        // Selection doesn't change past the last item.
+        assert_eq!(
+            table.perform(Cmd::Move(MoveDirection::Down)),
+            CmdResult::None
+        );
+        assert_eq!(table.state(), State::One(StateValue::Usize(2)));
+
+        assert_eq!(
+            table.perform(Cmd::Move(MoveDirection::Up)),
+            CmdResult::Changed(State::One(StateValue::Usize(1)))
+        );
+        assert_eq!(table.state(), State::One(StateValue::Usize(1)));
+    }
+
+    #[test]
+    fn test_table_page_navigation() {
+        let mut table = table(10);
+        table.page_size = 4;
+
+        assert_eq!(
+            table.perform(Cmd::Scroll(MoveDirection::Down)),
+            CmdResult::Changed(State::One(StateValue::Usize(4)))
+        );
+        assert_eq!(
+            table.perform(Cmd::Scroll(MoveDirection::Down)),
+            CmdResult::Changed(State::One(StateValue::Usize(8)))
+        );
+        // Scrolling is clamped to the last item.
+        assert_eq!(
+            table.perform(Cmd::Scroll(MoveDirection::Down)),
+            CmdResult::Changed(State::One(StateValue::Usize(9)))
+        );
+        assert_eq!(
+            table.perform(Cmd::Scroll(MoveDirection::Up)),
+            CmdResult::Changed(State::One(StateValue::Usize(5)))
+        );
+        assert_eq!(
+            table.perform(Cmd::GoTo(Position::Begin)),
+            CmdResult::Changed(State::One(StateValue::Usize(0)))
+        );
+        assert_eq!(table.perform(Cmd::GoTo(Position::Begin)), CmdResult::None);
+        assert_eq!(
+            table.perform(Cmd::GoTo(Position::End)),
+            CmdResult::Changed(State::One(StateValue::Usize(9)))
+        );
+    }
+
+    #[test]
+    fn test_table_submit() {
+        let mut table = table(3);
+        table.perform(Cmd::Move(MoveDirection::Down));
+
+        assert_eq!(
+            table.perform(Cmd::Submit),
+            CmdResult::Submit(State::One(StateValue::Usize(1)))
+        );
+
+        table.clear();
+        assert_eq!(table.perform(Cmd::Submit), CmdResult::None);
+    }
+
+    #[test]
+    fn test_table_set_items_clamps_selection() {
+        let mut table = table(5);
+        for _ in 0..4 {
+            table.perform(Cmd::Move(MoveDirection::Down));
+        }
+        assert_eq!(table.state(), State::One(StateValue::Usize(4)));
+
+        table.set_items(vec![Item(0), Item(1)]);
+        assert_eq!(table.items().len(), 2);
+        assert_eq!(table.state(), State::One(StateValue::Usize(1)));
+
+        table.clear();
+        assert!(table.items().is_empty());
+        assert_eq!(table.state(), State::None);
+
+        table.set_items(vec![Item(0), Item(1), Item(2)]);
+        assert_eq!(table.state(), State::One(StateValue::Usize(0)));
+    }
+
+    #[test]
+    fn test_table_sort() {
+        let items = [Item(1), Item(0), Item(2)];
+        let mut table = Widget::new(Table::new(
+            &items,
+            [label("#")],
+            [ColumnWidth::Grow],
+            theme::default_dark(),
+        ));
+        // Select `Item(0)`.
+        table.perform(Cmd::Move(MoveDirection::Down));
+
+        assert_eq!(
+            table.perform(Cmd::Custom(CMD_SORT_COLUMN)),
+            CmdResult::Changed(State::One(StateValue::Usize(0)))
+        );
+        assert_eq!(table.sort(), Some((0, Order::Ascending)));
+        assert!(table.items() == &vec![Item(0), Item(1), Item(2)]);
+
+        assert_eq!(
+            table.perform(Cmd::Custom(CMD_SORT_ORDER)),
+            CmdResult::Changed(State::One(StateValue::Usize(2)))
+        );
+        assert_eq!(table.sort(), Some((0, Order::Descending)));
+        assert!(table.items() == &vec![Item(2), Item(1), Item(0)]);
+
+        // New items are kept sorted.
+        table.set_items(vec![Item(3), Item(5), Item(4)]);
+        assert!(table.items() == &vec![Item(5), Item(4), Item(3)]);
+    }
+
+    #[test]
+    fn test_table_filter() {
+        let mut table = table(12);
+        table.perform(Cmd::GoTo(Position::End));
+        assert!(table.selection() == Some(&Item(11)));
+
+        // Typing is ignored unless filtering.
+        assert_eq!(table.perform(Cmd::Type('1')), CmdResult::None);
+
+        table.perform(Cmd::Custom(CMD_FILTER));
+        table.perform(Cmd::Type('1'));
+        assert!(table.is_filtering());
+        assert_eq!(table.filter(), "1");
+        // Rows "1", "10" and "11" are visible, and "11" is still selected.
+        assert_eq!(table.state(), State::One(StateValue::Usize(2)));
+        assert!(table.selection() == Some(&Item(11)));
+
+        table.perform(Cmd::Type('0'));
+        assert!(table.selection() == Some(&Item(10)));
+        assert_eq!(
+            table.perform(Cmd::Move(MoveDirection::Down)),
+            CmdResult::None
+        );
+
+        table.perform(Cmd::Custom(CMD_FILTER_APPLY));
+        assert!(!table.is_filtering());
+        assert_eq!(table.filter(), "10");
+
+        table.perform(Cmd::Cancel);
+        assert_eq!(table.filter(), "");
+        assert!(table.selection() == Some(&Item(10)));
+    }
+
+    #[test]
+    fn test_table_submit_sorted_and_filtered() {
+        let items = [Item(12), Item(1), Item(10), Item(2), Item(11)];
+        let mut table = Widget::new(Table::new(
+            &items,
+            [label("#")],
+            [ColumnWidth::Grow],
+            theme::default_dark(),
+        ));
+        table.perform(Cmd::Custom(CMD_SORT_COLUMN));
+        table.perform(Cmd::Custom(CMD_FILTER));
+        table.perform(Cmd::Type('1'));
+        table.perform(Cmd::Custom(CMD_FILTER_APPLY));
+
+        // Rows "1", "10", "11" and "12" are visible; highlight "11".
+        table.perform(Cmd::GoTo(Position::Begin));
+        table.perform(Cmd::Move(MoveDirection::Down));
+        table.perform(Cmd::Move(MoveDirection::Down));
+
+        // The submitted index is a position among the visible rows, while the
+        // selection is the highlighted item.
+        assert_eq!(
+            table.perform(Cmd::Submit),
+            CmdResult::Submit(State::One(StateValue::Usize(2)))
+        );
+        assert!(table.selection() == Some(&Item(11)));
+    }
+
+    #[test]
+    fn test_table_marks() {
+        let items = [Item(1), Item(0), Item(2)];
+        let mut table = Widget::new(Table::new(
+            &items,
+            [label("#")],
+            [ColumnWidth::Grow],
+            theme::default_dark(),
+        ));
+
+        table.perform(Cmd::Custom(CMD_TOGGLE_MARK));
+        table.perform(Cmd::GoTo(Position::End));
+        table.perform(Cmd::Custom(CMD_TOGGLE_MARK));
+        assert!(table.selections() == vec![&Item(1), &Item(2)]);
+        // The selection moves independently of marks.
+        assert!(table.selection() == Some(&Item(2)));
+
+        // Marks follow their items when sorting.
+        table.perform(Cmd::Custom(CMD_SORT_COLUMN));
+        table.perform(Cmd::Custom(CMD_SORT_ORDER));
+        assert!(table.selections() == vec![&Item(2), &Item(1)]);
+
+        table.perform(Cmd::Custom(CMD_TOGGLE_MARK));
+        assert!(table.selections() == vec![&Item(1)]);
+
+        table.set_items(vec![Item(3)]);
+        assert!(table.selections().is_empty());
+    }
+
+    #[test]
+    fn test_list_set_items_clamps_selection() {
+        let items = (0..3).map(Item).collect::<Vec<_>>();
+        let mut list = Widget::new(List::new(&items, Some(Item(2)), theme::default_dark()));
+        assert_eq!(list.state(), State::One(StateValue::Usize(2)));
+
+        list.set_items(vec![Item(0)]);
+        assert_eq!(list.state(), State::One(StateValue::Usize(0)));
+
+        list.clear();
+        assert_eq!(list.state(), State::None);
+    }
+
+    #[test]
+    fn test_list_select() {
+        let items = (0..3).map(Item).collect::<Vec<_>>();
+        let mut list = Widget::new(List::new(&items, None, theme::default_dark()));
+
+        assert_eq!(
+            list.select(2),
+            CmdResult::Changed(State::One(StateValue::Usize(2)))
+        );
+        assert_eq!(list.select(2), CmdResult::None);
+        assert_eq!(list.select(3), CmdResult::None);
+        assert_eq!(list.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_list_marks() {
+        let items = (0..3).map(Item).collect::<Vec<_>>();
+        let mut list = Widget::new(List::new(&items, None, theme::default_dark()));
+
+        list.perform(Cmd::Custom(CMD_TOGGLE_MARK));
+        list.perform(Cmd::Move(MoveDirection::Down));
+        list.perform(Cmd::Move(MoveDirection::Down));
+        assert_eq!(
+            list.perform(Cmd::Custom(CMD_TOGGLE_MARK)),
+            CmdResult::Changed(State::One(StateValue::Usize(2)))
+        );
+        assert!(list.selections() == vec![&Item(0), &Item(2)]);
+
+        list.perform(Cmd::Custom(CMD_TOGGLE_MARK));
+        assert!(list.selections() == vec![&Item(0)]);
+    }
+}
@@ -1,3 +1,7 @@
+use std::rc::Rc;
+
+use radicle::storage::ReadRepository;
+
 use tuirealm::command::{Cmd, CmdResult};
 use tuirealm::tui::layout::Rect;
 use tuirealm::{AttrValue, Attribute, Frame, MockComponent, Props, State};
@@ -12,6 +16,7 @@ use super::{Widget, WidgetComponent};
 use crate::cob;
 use crate::ui::cob::{IssueItem, PatchItem};
 use crate::ui::context::Context;
+use crate::ui::keymap::Keymap;
 use crate::ui::layout;
 use crate::ui::theme::Theme;
 
@@ -49,15 +54,15 @@ impl WidgetComponent for Dashboard {
 }
 
 pub struct IssueBrowser {
-    items: Vec<IssueItem>,
     table: Widget<Table<IssueItem, 7>>,
     shortcuts: Widget<Shortcuts>,
+    keymap: Keymap,
 }
 
 impl IssueBrowser {
     pub fn new(context: &Context, theme: &Theme, shortcuts: Widget<Shortcuts>) -> Self {
         let header = [
-            common::label(" ● "),
+            common::label(&format!(" {} ", theme.icons.state)),
             common::label("ID"),
             common::label("Title"),
             common::label("Author"),
@@ -94,14 +99,30 @@ impl IssueBrowser {
             .highlight(theme.colors.item_list_highlighted_bg);
 
         Self {
-            items,
             table,
             shortcuts,
+            keymap: *context.keymap(),
         }
     }
 
     pub fn items(&self) -> &Vec<IssueItem> {
-        &self.items
+        self.table.items()
+    }
+
+    pub fn selected_item(&self) -> Option<&IssueItem> {
+        self.table.selection()
+    }
+
+    pub fn marked_items(&self) -> Vec<&IssueItem> {
+        self.table.selections()
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        self.table.is_filtering()
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
     }
 }
 
@@ -128,15 +149,15 @@ impl WidgetComponent for IssueBrowser {
 }
 
 pub struct PatchBrowser {
-    items: Vec<PatchItem>,
     table: Widget<Table<PatchItem, 8>>,
     shortcuts: Widget<Shortcuts>,
+    keymap: Keymap,
 }
 
 impl PatchBrowser {
     pub fn new(context: &Context, theme: &Theme, shortcuts: Widget<Shortcuts>) -> Self {
         let header = [
-            common::label(" ● "),
+            common::label(&format!(" {} ", theme.icons.state)),
             common::label("ID"),
             common::label("Title"),
             common::label("Author"),
@@ -158,31 +179,56 @@ impl PatchBrowser {
         ];
 
         let repo = context.repository();
-        let mut items = vec![];
-
-        if let Ok(patches) = cob::patch::all(repo) {
-            for (id, patch) in patches {
-                if let Ok(item) = PatchItem::try_from((context.profile(), repo, id, patch)) {
-                    items.push(item);
-                }
-            }
-        }
-
-        items.sort_by(|a, b| b.timestamp().cmp(a.timestamp()));
-        items.sort_by(|a, b| a.state().cmp(b.state()));
+        // Nb. Items are cheap to build, since diff stats are only computed once a
+        // patch is displayed. They share the repository they're computed from; if
+        // it can't be opened, the patches are listed without stats.
+        let surf = radicle_surf::Repository::open(repo.path())
+            .ok()
+            .map(Rc::new);
+        let mut items = cob::patch::all(repo)
+            .map(|patches| {
+                patches
+                    .into_iter()
+                    .map(|(id, patch)| PatchItem::from((context.profile(), &surf, id, patch)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        items.sort_by(|a, b| {
+            a.state()
+                .status()
+                .cmp(&b.state().status())
+                .then(b.timestamp().cmp(a.timestamp()))
+        });
 
         let table = Widget::new(Table::new(&items, header, widths, theme.clone()))
             .highlight(theme.colors.item_list_highlighted_bg);
 
         Self {
-            items,
             table,
             shortcuts,
+            keymap: *context.keymap(),
         }
     }
 
     pub fn items(&self) -> &Vec<PatchItem> {
-        &self.items
+        self.table.items()
+    }
+
+    pub fn selected_item(&self) -> Option<&PatchItem> {
+        self.table.selection()
+    }
+
+    pub fn marked_items(&self) -> Vec<&PatchItem> {
+        self.table.selections()
+    }
+
+    pub fn is_filtering(&self) -> bool {
+        self.table.is_filtering()
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
     }
 }
 
@@ -219,19 +265,30 @@ pub fn navigation(theme: &Theme) -> Widget<Tabs> {
     )
 }
 
+/// The properties listed in the dashboard's about section, as name and value.
+fn about(context: &Context) -> Vec<(&'static str, String)> {
+    let (policy, scope) = match context.tracking() {
+        Ok(repo) => (repo.policy.to_string(), repo.scope.to_string()),
+        Err(_) => (String::from("n/a"), String::from("n/a")),
+    };
+    vec![
+        ("id", context.id().to_string()),
+        ("name", context.project().name().to_owned()),
+        ("description", context.project().description().to_owned()),
+        ("policy", policy),
+        ("scope", scope),
+    ]
+}
+
 pub fn dashboard(context: &Context, theme: &Theme) -> Widget<Dashboard> {
+    let properties = about(context)
+        .iter()
+        .map(|(name, value)| common::property(theme, name, value))
+        .collect();
     let about = common::labeled_container(
         theme,
         "about",
-        common::property_list(
-            theme,
-            vec![
-                common::property(theme, "id", &context.id().to_string()),
-                common::property(theme, "name", context.project().name()),
-                common::property(theme, "description", context.project().description()),
-            ],
-        )
-        .to_boxed(),
+        common::property_list(theme, properties).to_boxed(),
     );
     let shortcuts = common::shortcuts(
         theme,
@@ -250,8 +307,15 @@ pub fn patches(context: &Context, theme: &Theme) -> Widget<PatchBrowser> {
         theme,
         vec![
             common::shortcut(theme, "tab", "section"),
-            common::shortcut(theme, "↑/↓", "navigate"),
+            common::shortcut(
+                theme,
+                &format!("{}/{}", theme.icons.arrow_up, theme.icons.arrow_down),
+                "navigate",
+            ),
             common::shortcut(theme, "enter", "show"),
+            common::shortcut(theme, "s/o", "sort/order"),
+            common::shortcut(theme, "space", "mark"),
+            common::shortcut(theme, "/", "filter"),
             common::shortcut(theme, "q", "quit"),
         ],
     );
@@ -264,11 +328,103 @@ pub fn issues(context: &Context, theme: &Theme) -> Widget<IssueBrowser> {
         theme,
         vec![
             common::shortcut(theme, "tab", "section"),
-            common::shortcut(theme, "↑/↓", "navigate"),
+            common::shortcut(
+                theme,
+                &format!("{}/{}", theme.icons.arrow_up, theme.icons.arrow_down),
+                "navigate",
+            ),
             common::shortcut(theme, "enter", "show"),
+            common::shortcut(theme, "s/o", "sort/order"),
+            common::shortcut(theme, "space", "mark"),
+            common::shortcut(theme, "/", "filter"),
             common::shortcut(theme, "q", "quit"),
         ],
     );
 
     Widget::new(IssueBrowser::new(context, theme, shortcuts))
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use tuirealm::command::Direction;
+
+    use radicle::cob::issue::Issues;
+    use radicle::node::tracking::{self, Policy, Scope};
+    use radicle::node::{Alias, TRACKING_DB_FILE};
+    use radicle::profile::Home;
+    use radicle::storage::ReadStorage;
+    use radicle::test::fixtures;
+    use radicle::{git, rad, Profile};
+
+    use super::*;
+    use crate::ui::theme;
+
+    fn context(path: &Path) -> Context {
+        let home = Home::new(path.join("home")).unwrap();
+        let profile = Profile::init(home, Alias::new("alice"), None).unwrap();
+        let signer = profile.signer().unwrap();
+        let (working, _) = fixtures::repository(path.join("working"));
+        let (id, _, _) = rad::init(
+            &working,
+            "acme",
+            "Acme's repository",
+            git::refname!("master"),
+            &signer,
+            &profile.storage,
+        )
+        .unwrap();
+        let project = profile.storage.repository(id).unwrap().project().unwrap();
+
+        Context::new(profile, id, project)
+    }
+
+    #[test]
+    fn test_issue_browser_selected_item() {
+        let tmp = tempfile::tempdir().unwrap();
+        let context = context(tmp.path());
+        let signer = context.profile().signer().unwrap();
+        let mut issues = Issues::open(context.repository()).unwrap();
+
+        for title in ["First", "Second", "Third"] {
+            issues.create(title, "", &[], &[], &signer).unwrap();
+        }
+        let theme = theme::default_dark();
+        let shortcuts = common::shortcuts(&theme, vec![]);
+        let mut browser = Widget::new(IssueBrowser::new(&context, &theme, shortcuts));
+
+        browser.perform(Cmd::Move(Direction::Down));
+        assert!(matches!(browser.perform(Cmd::Submit), CmdResult::Submit(_)));
+
+        // The submitted item is the highlighted one.
+        let highlighted = &browser.items()[1];
+        assert_eq!(
+            browser.selected_item().map(|i| i.id()),
+            Some(highlighted.id())
+        );
+    }
+
+    #[test]
+    fn test_dashboard_tracking() {
+        let tmp = tempfile::tempdir().unwrap();
+        let context = context(tmp.path());
+        let property = |name| {
+            about(&context)
+                .into_iter()
+                .find_map(|(n, value)| (n == name).then_some(value))
+                .unwrap()
+        };
+        let db = context.profile().home.node().join(TRACKING_DB_FILE);
+        let mut tracking = tracking::store::Config::open(db).unwrap();
+
+        // Without an entry for the repository, the node's defaults are shown.
+        let config = &context.profile().config.node;
+        assert_eq!(property("policy"), config.policy.to_string());
+        assert_eq!(property("scope"), config.scope.to_string());
+
+        tracking.track_repo(context.id(), Scope::All).unwrap();
+        assert_eq!(property("policy"), Policy::Track.to_string());
+        assert_eq!(property("scope"), Scope::All.to_string());
+    }
+}
@@ -14,6 +14,7 @@ use super::Widget;
 use crate::ui::cob;
 use crate::ui::cob::IssueItem;
 use crate::ui::context::Context;
+use crate::ui::keymap::Keymap;
 use crate::ui::theme::Theme;
 use crate::ui::widget::common::context::ContextBar;
 
@@ -22,6 +23,7 @@ use super::*;
 pub struct LargeList {
     items: Vec<IssueItem>,
     list: Widget<LabeledContainer>,
+    keymap: Keymap,
 }
 
 impl LargeList {
@@ -47,9 +49,14 @@ impl LargeList {
         Self {
             items,
             list: container,
+            keymap: *context.keymap(),
         }
     }
 
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
     pub fn items(&self) -> &Vec<IssueItem> {
         &self.items
     }
@@ -3,7 +3,7 @@ use radicle::Profile;
 
 use radicle_cli::terminal::format;
 
-use tuirealm::command::{Cmd, CmdResult};
+use tuirealm::command::{Cmd, CmdResult, Direction};
 use tuirealm::props::Color;
 use tuirealm::tui::layout::Rect;
 use tuirealm::{AttrValue, Attribute, Frame, MockComponent, Props, State};
@@ -14,7 +14,11 @@ use super::common;
 use super::common::container::Tabs;
 use super::common::context::{ContextBar, Shortcuts};
 use super::common::label::Label;
+use super::common::list::List;
 
+use crate::ui::cob::DiffItem;
+use crate::ui::context::Context;
+use crate::ui::keymap::Keymap;
 use crate::ui::theme::Theme;
 use crate::ui::{cob, layout};
 
@@ -72,33 +76,70 @@ impl WidgetComponent for Activity {
     }
 }
 
+/// The diff of the latest patch revision, with one line per item.
 pub struct Files {
-    label: Widget<Label>,
+    diff: Widget<List<DiffItem>>,
     context: Widget<ContextBar>,
     shortcuts: Widget<Shortcuts>,
+    keymap: Keymap,
 }
 
 impl Files {
     pub fn new(
-        label: Widget<Label>,
+        diff: Widget<List<DiffItem>>,
         context: Widget<ContextBar>,
         shortcuts: Widget<Shortcuts>,
+        keymap: Keymap,
     ) -> Self {
         Self {
-            label,
+            diff,
             context,
             shortcuts,
+            keymap,
+        }
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Selects the header of the next changed file, if any.
+    fn select_next_file(&mut self) -> CmdResult {
+        let start = self.diff.selected().map(|i| i + 1).unwrap_or_default();
+        let next = self
+            .diff
+            .items()
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, item)| item.is_file())
+            .map(|(index, _)| index);
+
+        match next {
+            Some(index) => self.diff.select(index),
+            None => CmdResult::None,
+        }
+    }
+
+    /// Selects the header of the previous changed file, if any.
+    fn select_previous_file(&mut self) -> CmdResult {
+        let end = self.diff.selected().unwrap_or_default();
+        let previous = self
+            .diff
+            .items()
+            .iter()
+            .take(end)
+            .rposition(|item| item.is_file());
+
+        match previous {
+            Some(index) => self.diff.select(index),
+            None => CmdResult::None,
         }
     }
 }
 
 impl WidgetComponent for Files {
     fn view(&mut self, _properties: &Props, frame: &mut Frame, area: Rect) {
-        let label_w = self
-            .label
-            .query(Attribute::Width)
-            .unwrap_or(AttrValue::Size(1))
-            .unwrap_size();
         let context_h = self
             .context
             .query(Attribute::Height)
@@ -111,18 +152,21 @@ impl WidgetComponent for Files {
             .unwrap_size();
         let layout = layout::root_component_with_context(area, context_h, shortcuts_h);
 
-        self.label
-            .view(frame, layout::centered_label(label_w, layout[0]));
+        self.diff.view(frame, layout[0]);
         self.context.view(frame, layout[1]);
         self.shortcuts.view(frame, layout[2]);
     }
 
     fn state(&self) -> State {
-        State::None
+        self.diff.state()
     }
 
-    fn perform(&mut self, _properties: &Props, _cmd: Cmd) -> CmdResult {
-        CmdResult::None
+    fn perform(&mut self, _properties: &Props, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(Direction::Right) => self.select_next_file(),
+            Cmd::Move(Direction::Left) => self.select_previous_file(),
+            _ => self.diff.perform(cmd),
+        }
     }
 }
 
@@ -154,20 +198,27 @@ pub fn activity(theme: &Theme, patch: (PatchId, &Patch), profile: &Profile) -> W
     Widget::new(activity)
 }
 
-pub fn files(theme: &Theme, patch: (PatchId, &Patch), profile: &Profile) -> Widget<Activity> {
+pub fn files(context: &Context, theme: &Theme, patch: (PatchId, &Patch)) -> Widget<Files> {
     let (id, patch) = patch;
     let shortcuts = common::shortcuts(
         theme,
         vec![
             common::shortcut(theme, "esc", "back"),
             common::shortcut(theme, "tab", "section"),
+            common::shortcut(theme, "left/right", "file"),
             common::shortcut(theme, "q", "quit"),
         ],
     );
-    let context = context(theme, (id, patch), profile);
-
-    let not_implemented = common::label("not implemented").foreground(theme.colors.default_fg);
-    let files = Activity::new(not_implemented, context, shortcuts);
+    let context_bar = self::context(theme, (id, patch), context.profile());
+
+    let items = match DiffItem::all(context.repository(), patch) {
+        Ok(items) if items.is_empty() => vec![DiffItem::Note(String::from("no changes"))],
+        Ok(items) => items,
+        Err(err) => vec![DiffItem::Note(format!("failed to load diff: {err}"))],
+    };
+    let diff = Widget::new(List::new(&items, None, theme.clone()))
+        .highlight(theme.colors.item_list_highlighted_bg);
+    let files = Files::new(diff, context_bar, shortcuts, *context.keymap());
 
     Widget::new(files)
 }
@@ -27,17 +27,89 @@ pub fn column_widths(area: Rect, widths: &[ColumnWidth], spacing: u16) -> Vec<Co
         .checked_div(grow_count)
         .unwrap_or(0);
 
+    // If the fixed columns don't fit into the available width, the percentages
+    // are capped, so that columns further to the right get truncated instead of
+    // overlapping.
+    let mut remaining = 100u16;
+
     widths
         .iter()
-        .map(|width| match width {
-            ColumnWidth::Fixed(w) => {
-                let p: f64 = *w as f64 / area.width as f64 * 100_f64;
-                Constraint::Percentage(p.ceil() as u16)
-            }
-            ColumnWidth::Grow => {
-                let p: f64 = grow_width as f64 / area.width as f64 * 100_f64;
-                Constraint::Percentage(p.floor() as u16)
-            }
+        .map(|width| {
+            let p = match width {
+                ColumnWidth::Fixed(w) => {
+                    let p: f64 = *w as f64 / area.width as f64 * 100_f64;
+                    p.ceil() as u16
+                }
+                ColumnWidth::Grow => {
+                    let p: f64 = grow_width as f64 / area.width as f64 * 100_f64;
+                    p.floor() as u16
+                }
+            };
+            let p = p.min(remaining);
+            remaining -= p;
+
+            Constraint::Percentage(p)
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use tuirealm::props::BorderSides;
+    use tuirealm::tui::layout::{Direction, Layout};
+    use tuirealm::tui::widgets::Block;
+
+    use super::*;
+
+    fn percentages(constraints: &[Constraint]) -> Vec<u16> {
+        constraints
+            .iter()
+            .map(|c| match c {
+                Constraint::Percentage(p) => *p,
+                _ => panic!("unexpected constraint {c:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_column_widths_fit_area() {
+        let widths = [
+            ColumnWidth::Fixed(3),
+            ColumnWidth::Fixed(7),
+            ColumnWidth::Grow,
+            ColumnWidth::Fixed(21),
+            ColumnWidth::Fixed(18),
+        ];
+
+        for width in [10, 20, 40, 60, 80, 120, 200] {
+            let area = Rect::new(0, 0, width, 10);
+            // The areas the header and body tables are rendered in.
+            let header_area = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![Constraint::Min(1)])
+                .vertical_margin(1)
+                .horizontal_margin(1)
+                .split(area)[0];
+            let body_area = Block::default()
+                .borders(BorderSides::BOTTOM | BorderSides::LEFT | BorderSides::RIGHT)
+                .inner(area);
+
+            let header = percentages(&column_widths(header_area, &widths, 2));
+            let body = percentages(&column_widths(body_area, &widths, 2));
+
+            assert_eq!(header, body, "header and body differ at width {width}");
+            assert!(
+                header.iter().sum::<u16>() <= 100,
+                "columns overflow at width {width}: {header:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_column_widths_fixed() {
+        let widths = [ColumnWidth::Fixed(10), ColumnWidth::Grow];
+        let area = Rect::new(0, 0, 100, 10);
+
+        assert_eq!(percentages(&column_widths(area, &widths, 0)), vec![10, 90]);
+    }
+}
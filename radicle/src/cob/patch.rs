@@ -799,6 +799,41 @@ impl fmt::Display for State {
     }
 }
 
+impl State {
+    /// The status of the patch, without any state-specific data.
+    pub fn status(&self) -> Status {
+        match self {
+            Self::Draft => Status::Draft,
+            Self::Open { .. } => Status::Open,
+            Self::Archived => Status::Archived,
+            Self::Merged { .. } => Status::Merged,
+        }
+    }
+}
+
+/// Patch status, ie. a [`State`] without its data.
+///
+/// Statuses are ordered by their listing priority: drafts come first, followed by open,
+/// archived and merged patches.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    Draft,
+    Open,
+    Archived,
+    Merged,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Archived => write!(f, "archived"),
+            Self::Draft => write!(f, "draft"),
+            Self::Open => write!(f, "open"),
+            Self::Merged => write!(f, "merged"),
+        }
+    }
+}
+
 /// A merged patch revision.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "camelCase")]
@@ -1454,6 +1489,20 @@ where
             .filter(|(_, p, _)| p.is_open()))
     }
 
+    /// Get patches with the given status, or all patches if `None`. Patches that fail
+    /// to load are skipped.
+    pub fn filtered(
+        &self,
+        status: Option<Status>,
+    ) -> Result<impl Iterator<Item = (PatchId, Patch, clock::Lamport)> + '_, Error> {
+        let all = self.all()?;
+
+        Ok(all
+            .into_iter()
+            .filter_map(|result| result.ok())
+            .filter(move |(_, p, _)| status.map_or(true, |s| p.state().status() == s)))
+    }
+
     /// Get patches proposed by the given key.
     pub fn proposed_by<'b>(
         &'b self,
@@ -1847,6 +1896,53 @@ mod test {
         assert_eq!(merge.commit, pr.base);
     }
 
+    #[test]
+    fn test_patches_filtered() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ctx = test::setup::Context::new(&tmp);
+        let signer = &ctx.signer;
+        let pr = ctx.branch_with(test::setup::initial_blobs());
+        let mut patches = Patches::open(&ctx.project).unwrap();
+        let open = patches
+            .create(
+                "My first patch",
+                "Blah blah blah.",
+                MergeTarget::Delegates,
+                pr.base,
+                pr.oid,
+                &[],
+                signer,
+            )
+            .unwrap()
+            .id;
+        let draft = patches
+            .draft(
+                "My second patch",
+                "Blah blah blah.",
+                MergeTarget::Delegates,
+                pr.base,
+                pr.oid,
+                &[],
+                signer,
+            )
+            .unwrap()
+            .id;
+
+        let ids = |status| {
+            patches
+                .filtered(status)
+                .unwrap()
+                .map(|(id, _, _)| id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(Some(Status::Open)), vec![open]);
+        assert_eq!(ids(Some(Status::Draft)), vec![draft]);
+        assert!(ids(Some(Status::Merged)).is_empty());
+        assert_eq!(ids(None).len(), 2);
+        assert!(Status::Draft < Status::Open);
+        assert!(Status::Archived < Status::Merged);
+    }
+
     #[test]
     fn test_patch_merge_and_archive() {
         let rid = gen::<Id>(1);
@@ -183,6 +183,7 @@ where
                 typename: T::type_name().clone(),
                 message: message.to_owned(),
                 changes,
+                expected_head: None,
             },
         )?;
 
@@ -363,7 +364,7 @@ impl<T: FromHistory> Transaction<T> {
     {
         let actions = NonEmpty::from_vec(self.actions)
             .expect("Transaction::commit: transaction must not be empty");
-        let Updated { head, object } = store.update(id, msg, actions.clone(), signer)?;
+        let Updated { head, object, .. } = store.update(id, msg, actions.clone(), signer)?;
         let id = EntryId::from(head);
         let author = self.actor;
         let timestamp = object.history().timestamp().into();
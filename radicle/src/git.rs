@@ -93,7 +93,12 @@ impl std::str::FromStr for Version {
 
 /// Get the system's git version.
 pub fn version() -> Result<Version, VersionError> {
-    let output = Command::new("git").arg("version").output()?;
+    version_of(Path::new("git"))
+}
+
+/// Get the version of the git binary at the given path.
+pub fn version_of(git: &Path) -> Result<Version, VersionError> {
+    let output = Command::new(git).arg("version").output()?;
 
     if output.status.success() {
         let output = String::from_utf8(output.stdout)?;
@@ -106,6 +111,30 @@ pub fn version() -> Result<Version, VersionError> {
     ))
 }
 
+/// Run `git gc --auto` on the repository at the given path, using the given git binary.
+///
+/// Git only packs loose objects and consolidates packs if it deems it necessary, based on
+/// the repository's `gc.auto` and `gc.autoPackLimit` settings. The user's global and system
+/// configuration is ignored.
+pub fn gc_auto(git: &Path, repo: &Path) -> io::Result<()> {
+    let output = Command::new(git)
+        .env_clear()
+        .envs(std::env::vars().filter(|(k, _)| k == "PATH" || k.starts_with("GIT_TRACE")))
+        .envs(env::GIT_DEFAULT_CONFIG)
+        .arg("--git-dir")
+        .arg(repo)
+        .args(["gc", "--auto", "--quiet"])
+        .output()?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+    ))
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum RefError {
     #[error("ref name is not valid UTF-8")]
@@ -720,4 +749,41 @@ mod test {
 
         assert!(Version::from_str("2.34").is_err());
     }
+
+    #[test]
+    fn test_gc_auto() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init_bare(tmp.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_i32("gc.auto", 1).unwrap();
+        config.set_bool("gc.autoDetach", false).unwrap();
+
+        let mut tree = repo.treebuilder(None).unwrap();
+        for i in 0..1000 {
+            let blob = repo.blob(format!("blob {i}").as_bytes()).unwrap();
+            tree.insert(format!("{i}"), blob, 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(tree.write().unwrap()).unwrap();
+        let sig = git2::Signature::now("anonymous", "anonymous@radicle.xyz").unwrap();
+        repo.commit(Some("refs/heads/master"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+
+        let loose = |repo: &git2::Repository| {
+            std::fs::read_dir(repo.path().join("objects"))
+                .unwrap()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().len() == 2)
+                .flat_map(|e| std::fs::read_dir(e.path()).unwrap())
+                .count()
+        };
+        assert!(loose(&repo) > 1000);
+
+        gc_auto(Path::new("git"), repo.path()).unwrap();
+
+        assert_eq!(loose(&repo), 0);
+        assert!(std::fs::read_dir(repo.path().join("objects").join("pack"))
+            .unwrap()
+            .next()
+            .is_some());
+    }
 }
@@ -426,12 +426,34 @@ pub enum AnnounceEvent {
     Announced,
 }
 
+/// Severity of a [`Validation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    /// The remote is behind or otherwise harmless to skip.
+    Warning,
+    /// The remote's data is invalid, eg. its signed refs don't verify.
+    Error,
+}
+
+/// A remote that failed validation during a fetch. Its refs were not updated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Validation {
+    pub remote: NodeId,
+    pub severity: Severity,
+    pub reason: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "status", rename_all = "kebab-case")]
 pub enum FetchResult {
     Success {
         updated: Vec<RefUpdate>,
         namespaces: HashSet<NodeId>,
+        /// Remotes that were skipped because they failed validation.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        validations: Vec<Validation>,
     },
     // TODO: Create enum for reason.
     Failed {
@@ -449,18 +471,58 @@ impl FetchResult {
             Self::Success {
                 updated,
                 namespaces,
+                ..
             } => Some((updated, namespaces)),
             _ => None,
         }
     }
+
+    /// Validations of the given severity.
+    fn validations(&self, severity: Severity) -> impl Iterator<Item = &Validation> {
+        let validations = match self {
+            Self::Success { validations, .. } => validations.as_slice(),
+            Self::Failed { .. } => &[],
+        };
+        validations.iter().filter(move |v| v.severity == severity)
+    }
+
+    /// Remotes that were skipped because their data is invalid.
+    pub fn errors(&self) -> impl Iterator<Item = &Validation> {
+        self.validations(Severity::Error)
+    }
+
+    /// Remotes that were skipped for benign reasons.
+    pub fn warnings(&self) -> impl Iterator<Item = &Validation> {
+        self.validations(Severity::Warning)
+    }
+
+    /// Turn a successful result with [`FetchResult::errors`] into a failure, for callers
+    /// that can't accept any remote failing validation.
+    pub fn into_strict(self) -> Self {
+        let reason = self
+            .errors()
+            .map(|v| format!("{}: {}", v.remote, v.reason))
+            .collect::<Vec<_>>();
+
+        if reason.is_empty() {
+            self
+        } else {
+            Self::Failed {
+                reason: format!("remotes failed validation: {}", reason.join("; ")),
+            }
+        }
+    }
 }
 
-impl<S: ToString> From<Result<(Vec<RefUpdate>, HashSet<NodeId>), S>> for FetchResult {
-    fn from(value: Result<(Vec<RefUpdate>, HashSet<NodeId>), S>) -> Self {
+impl<S: ToString> From<Result<(Vec<RefUpdate>, HashSet<NodeId>, Vec<Validation>), S>>
+    for FetchResult
+{
+    fn from(value: Result<(Vec<RefUpdate>, HashSet<NodeId>, Vec<Validation>), S>) -> Self {
         match value {
-            Ok((updated, namespaces)) => Self::Success {
+            Ok((updated, namespaces, validations)) => Self::Success {
                 updated,
                 namespaces,
+                validations,
             },
             Err(err) => Self::Failed {
                 reason: err.to_string(),
@@ -490,6 +552,7 @@ impl FetchResults {
             if let FetchResult::Success {
                 updated,
                 namespaces,
+                ..
             } = r
             {
                 Some((nid, updated.as_slice(), namespaces.clone()))
@@ -920,4 +983,34 @@ mod test {
         assert!(Alias::from_str("cloud head").is_err());
         assert!(Alias::from_str("cloudhead\n").is_err());
     }
+
+    #[test]
+    fn test_fetch_result_validations() {
+        let remote = crate::test::arbitrary::gen::<NodeId>(1);
+        let result = FetchResult::Success {
+            updated: vec![],
+            namespaces: HashSet::new(),
+            validations: vec![
+                Validation {
+                    remote,
+                    severity: Severity::Error,
+                    reason: "signed refs have diverged".to_owned(),
+                },
+                Validation {
+                    remote,
+                    severity: Severity::Warning,
+                    reason: "signed refs are behind ours".to_owned(),
+                },
+            ],
+        };
+        assert_eq!(result.errors().count(), 1);
+        assert_eq!(result.warnings().count(), 1);
+        assert!(!result.into_strict().is_success());
+
+        // Results without validations are still understood.
+        let result: FetchResult =
+            json::from_str(r#"{"status":"success","updated":[],"namespaces":[]}"#).unwrap();
+        assert_eq!(result.errors().count(), 0);
+        assert!(result.into_strict().is_success());
+    }
 }
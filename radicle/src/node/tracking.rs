@@ -94,7 +94,7 @@ pub enum Scope {
     /// Track remotes of nodes that are already tracked.
     #[default]
     Trusted,
-    /// Track all remotes.
+    /// Track all remotes, except those of blocked nodes.
     All,
 }
 
@@ -121,7 +121,7 @@ pub enum FetchError {
 pub type RemoteId = PublicKey;
 
 /// An update to a reference.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum RefUpdate {
     Updated { name: RefString, old: Oid, new: Oid },